@@ -0,0 +1,51 @@
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use crate::core::concurrent_ring_buffer::ConcurrentRingBuffer;
+
+const CAPACITY: usize = 800;
+const APPENDS_PER_THREAD: usize = 10_000;
+
+fn mutex_vecdeque(threads: usize) {
+    let buffer = Arc::new(Mutex::new(VecDeque::with_capacity(CAPACITY)));
+    std::thread::scope(|scope| {
+        for t in 0..threads {
+            let buffer = buffer.clone();
+            scope.spawn(move || {
+                for i in 0..APPENDS_PER_THREAD {
+                    let mut buffer = buffer.lock().unwrap();
+                    if buffer.len() == CAPACITY { buffer.pop_front(); }
+                    buffer.push_back(t * APPENDS_PER_THREAD + i);
+                    if i % 100 == 0 { criterion::black_box(buffer.iter().cloned().collect::<Vec<_>>()); }
+                }
+            });
+        }
+    });
+}
+
+fn concurrent_ring_buffer(threads: usize) {
+    let buffer = ConcurrentRingBuffer::new(CAPACITY);
+    std::thread::scope(|scope| {
+        for t in 0..threads {
+            let buffer = buffer.clone();
+            scope.spawn(move || {
+                for i in 0..APPENDS_PER_THREAD {
+                    buffer.append(t * APPENDS_PER_THREAD + i);
+                    if i % 100 == 0 { criterion::black_box(buffer.snapshot()); }
+                }
+            });
+        }
+    });
+}
+
+fn bench_contention(c: &mut Criterion) {
+    let mut group = c.benchmark_group("ring_buffer_contention");
+    for threads in [1, 2, 4, 8] {
+        group.bench_with_input(BenchmarkId::new("mutex_vecdeque", threads), &threads, |b, &t| b.iter(|| mutex_vecdeque(t)));
+        group.bench_with_input(BenchmarkId::new("concurrent_ring_buffer", threads), &threads, |b, &t| b.iter(|| concurrent_ring_buffer(t)));
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_contention);
+criterion_main!(benches);
@@ -0,0 +1,44 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+use crate::quantum::field_rules::{FieldRule, Neighborhood, QuantumDecay};
+use crate::quantum::quantum_fractal_tensor_engine::QuantumFractalTensorEngine;
+
+// The pre-FieldTensor tick: nested vectors, one full clone per shard up front and one per
+// transform, and the field decayed in place of a real shard merge.
+fn legacy_tick(tensor_field: &mut Vec<Vec<f64>>) {
+    let mut shards = vec![tensor_field.clone(); 2];
+    for shard in shards.iter_mut() {
+        for _ in 0..8 {
+            let mut new_field = shard.to_vec();
+            for i in 0..200 {
+                for j in 0..200 {
+                    let noise = rand::random::<f64>() * 0.16 - 0.08;
+                    new_field[i][j] = shard[i][j] * 0.5 + noise * 0.15;
+                }
+            }
+            *shard = new_field;
+        }
+    }
+    *tensor_field = shards[0].clone();
+}
+
+struct PerCellDecay(QuantumDecay);
+
+impl FieldRule<2> for PerCellDecay {
+    fn name(&self) -> &'static str { "per_cell_decay" }
+    fn update(&self, cell: &Neighborhood<'_, 2>, out: &mut [f64]) { self.0.update(cell, out) }
+}
+
+fn bench_tick(c: &mut Criterion) {
+    let mut group = c.benchmark_group("tensor_field_tick_200x200x8");
+    let mut legacy = vec![vec![0.0; 200]; 200];
+    group.bench_function("legacy_nested_vec", |b| b.iter(|| legacy_tick(&mut legacy)));
+    let mut engine = QuantumFractalTensorEngine::new();
+    group.bench_function("row_kernel", |b| b.iter(|| engine.evolve(8)));
+    let mut per_cell = QuantumFractalTensorEngine::new();
+    per_cell.set_rule(PerCellDecay(QuantumDecay::new()));
+    group.bench_function("per_cell", |b| b.iter(|| per_cell.evolve(8)));
+    group.finish();
+}
+
+criterion_group!(benches, bench_tick);
+criterion_main!(benches);
@@ -0,0 +1,160 @@
+use chrono::{DateTime, Duration, DurationRound, Utc};
+use crate::core::ring_buffer::{RingBuffer, Timestamped};
+use crate::storage::state_manager::StateManager;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ChaosSample {
+    pub timestamp: DateTime<Utc>,
+    pub values: Vec<f64>,
+}
+
+impl ChaosSample {
+    // The scalar chaos level that rollups and estimators work on.
+    pub fn total(&self) -> f64 { self.values.iter().sum() }
+}
+
+impl Timestamped for ChaosSample {
+    fn timestamp(&self) -> DateTime<Utc> { self.timestamp }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Resolution {
+    Raw,
+    Minute,
+    Hour,
+}
+
+impl Resolution {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Resolution::Raw => "raw",
+            Resolution::Minute => "minute",
+            Resolution::Hour => "hour",
+        }
+    }
+
+    fn bucket_start(&self, timestamp: DateTime<Utc>) -> DateTime<Utc> {
+        match self {
+            Resolution::Raw => timestamp,
+            Resolution::Minute => timestamp.duration_trunc(Duration::minutes(1)).unwrap(),
+            Resolution::Hour => timestamp.duration_trunc(Duration::hours(1)).unwrap(),
+        }
+    }
+}
+
+// min/max/mean of the sample totals whose timestamps fall in the bucket starting at `start`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Rollup {
+    pub start: DateTime<Utc>,
+    pub count: usize,
+    pub min: f64,
+    pub max: f64,
+    pub mean: f64,
+}
+
+impl Rollup {
+    pub fn of(start: DateTime<Utc>, value: f64) -> Self {
+        Self { start, count: 1, min: value, max: value, mean: value }
+    }
+
+    pub fn absorb(&mut self, value: f64) {
+        self.count += 1;
+        self.min = self.min.min(value);
+        self.max = self.max.max(value);
+        self.mean += (value - self.mean) / self.count as f64;
+    }
+}
+
+impl Timestamped for Rollup {
+    fn timestamp(&self) -> DateTime<Utc> { self.start }
+}
+
+// Bounded history of chaos samples with minute and hour rollups kept alongside, so old raw
+// samples can be evicted in O(1) while coarse history survives for days. Samples are assumed
+// to arrive in time order. With persistence on, every sample writes its open rollups through to
+// SQLite, so a restart in the middle of a bucket resumes it instead of losing it.
+#[derive(Clone)]
+pub struct ChaosTimeSeries {
+    raw: RingBuffer<ChaosSample>,
+    minutes: RingBuffer<Rollup>,
+    hours: RingBuffer<Rollup>,
+    open_minute: Option<Rollup>,
+    open_hour: Option<Rollup>,
+    persistence: Option<StateManager>,
+}
+
+impl ChaosTimeSeries {
+    pub fn new(raw_capacity: usize, minute_capacity: usize, hour_capacity: usize) -> Self {
+        Self {
+            raw: RingBuffer::new(raw_capacity),
+            minutes: RingBuffer::new(minute_capacity),
+            hours: RingBuffer::new(hour_capacity),
+            open_minute: None,
+            open_hour: None,
+            persistence: None,
+        }
+    }
+
+    // Restores previously persisted rollups, reopening the newest bucket of each resolution, and
+    // writes every rollup update from now on.
+    pub fn with_persistence(mut self, manager: StateManager) -> Self {
+        let mut minutes = manager.load_rollups(Resolution::Minute.name());
+        self.open_minute = minutes.pop();
+        self.minutes.extend(minutes);
+        let mut hours = manager.load_rollups(Resolution::Hour.name());
+        self.open_hour = hours.pop();
+        self.hours.extend(hours);
+        self.persistence = Some(manager);
+        self
+    }
+
+    pub fn len(&self) -> usize { self.raw.len() }
+
+    pub fn is_empty(&self) -> bool { self.raw.is_empty() }
+
+    pub fn latest(&self) -> Option<&ChaosSample> { self.raw.get_latest() }
+
+    pub fn record(&mut self, values: Vec<f64>) { self.record_at(Utc::now(), values); }
+
+    pub fn record_at(&mut self, timestamp: DateTime<Utc>, values: Vec<f64>) {
+        let sample = ChaosSample { timestamp, values };
+        let total = sample.total();
+        self.raw.append(sample);
+        Self::roll(Resolution::Minute, &mut self.open_minute, &mut self.minutes, &self.persistence, timestamp, total);
+        Self::roll(Resolution::Hour, &mut self.open_hour, &mut self.hours, &self.persistence, timestamp, total);
+    }
+
+    fn roll(resolution: Resolution, open: &mut Option<Rollup>, closed: &mut RingBuffer<Rollup>, persistence: &Option<StateManager>, timestamp: DateTime<Utc>, value: f64) {
+        let start = resolution.bucket_start(timestamp);
+        let rollup = match open {
+            Some(rollup) if rollup.start == start => {
+                rollup.absorb(value);
+                rollup
+            }
+            _ => {
+                if let Some(finished) = open.take() { closed.append(finished); }
+                open.insert(Rollup::of(start, value))
+            }
+        };
+        if let Some(manager) = persistence { manager.save_rollup(resolution.name(), rollup); }
+    }
+
+    // Raw sample totals, oldest first.
+    pub fn totals(&self) -> Vec<f64> { self.raw.iter().map(ChaosSample::total).collect() }
+
+    pub fn iter(&self) -> impl Iterator<Item = &ChaosSample> { self.raw.iter() }
+
+    // Raw samples with `from <= timestamp < to`.
+    pub fn range(&self, from: DateTime<Utc>, to: DateTime<Utc>) -> impl Iterator<Item = &ChaosSample> { self.raw.window(from, to) }
+
+    // Buckets starting in `[from, to)` at the given resolution, including the still-open one;
+    // at `Raw` every sample is its own single-count bucket.
+    pub fn rollups(&self, resolution: Resolution, from: DateTime<Utc>, to: DateTime<Utc>) -> Vec<Rollup> {
+        let (closed, open) = match resolution {
+            Resolution::Raw => return self.range(from, to).map(|sample| Rollup::of(sample.timestamp, sample.total())).collect(),
+            Resolution::Minute => (&self.minutes, self.open_minute),
+            Resolution::Hour => (&self.hours, self.open_hour),
+        };
+        closed.window(from, to).copied().chain(open.filter(|rollup| from <= rollup.start && rollup.start < to)).collect()
+    }
+}
@@ -0,0 +1,191 @@
+use std::collections::HashSet;
+use std::time::{Duration, Instant};
+use crate::core::text_pipeline::{keyword_overlap, normalize, tokenize, QueryAnalysis};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct CoherenceScore {
+    // Fraction of the query's keywords the response mentions (1 when the query has none).
+    pub keyword_overlap: f64,
+    // Fraction of words that repeat an earlier word in the response.
+    pub repetition: f64,
+    // 1 inside the word-count bounds, falling off proportionally outside them.
+    pub length_fit: f64,
+    // Weighted sum of overlap, length fit and (1 - repetition), in [0, 1].
+    pub total: f64,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct CoherenceScorer {
+    pub overlap_weight: f64,
+    pub length_weight: f64,
+    pub repetition_weight: f64,
+    pub min_words: usize,
+    pub max_words: usize,
+}
+
+impl CoherenceScorer {
+    pub fn new() -> Self {
+        Self { overlap_weight: 0.5, length_weight: 0.3, repetition_weight: 0.2, min_words: 8, max_words: 60 }
+    }
+
+    pub fn score(&self, analysis: &QueryAnalysis, response: &str) -> CoherenceScore {
+        let tokens = tokenize(&normalize(response));
+        let keyword_overlap = if analysis.keywords.is_empty() { 1.0 } else { keyword_overlap(&analysis.keywords, &tokens) };
+        let distinct: HashSet<&String> = tokens.iter().collect();
+        let repetition = if tokens.is_empty() { 0.0 } else { 1.0 - distinct.len() as f64 / tokens.len() as f64 };
+        let words = tokens.len();
+        let length_fit = if words < self.min_words {
+            words as f64 / self.min_words as f64
+        } else if words > self.max_words {
+            self.max_words as f64 / words as f64
+        } else {
+            1.0
+        };
+        let weights = self.overlap_weight + self.length_weight + self.repetition_weight;
+        let total = (self.overlap_weight * keyword_overlap + self.length_weight * length_fit + self.repetition_weight * (1.0 - repetition)) / weights;
+        CoherenceScore { keyword_overlap, repetition, length_fit, total }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RefinementBudget {
+    pub max_depth: usize,
+    pub max_duration: Duration,
+    // Stop as soon as the best score reaches this.
+    pub target: f64,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StopReason {
+    TargetReached,
+    DepthExhausted,
+    TimeExhausted,
+    // No strategy produced a better candidate at the last depth.
+    Converged,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct RefinementAttempt {
+    pub depth: usize,
+    pub strategy: &'static str,
+    pub candidate: String,
+    pub score: CoherenceScore,
+    pub elapsed: Duration,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct RefinementTrace {
+    // Every candidate scored, in order; attempt 0 is the unrefined response.
+    pub attempts: Vec<RefinementAttempt>,
+    pub best: usize,
+    pub stop_reason: StopReason,
+}
+
+impl RefinementTrace {
+    pub fn best_attempt(&self) -> &RefinementAttempt { &self.attempts[self.best] }
+
+    pub fn best_response(&self) -> &str { &self.best_attempt().candidate }
+}
+
+const STRATEGIES: [&str; 4] = ["ground_keywords", "deduplicate", "trim", "elaborate"];
+
+// Each depth applies every strategy to the best candidate so far and keeps the highest scorer.
+// `grounding` is text the response may draw on, such as the retrieved dialogue turns.
+pub fn refine(scorer: &CoherenceScorer, analysis: &QueryAnalysis, initial: String, grounding: &[&str], budget: &RefinementBudget) -> RefinementTrace {
+    refine_with(scorer, analysis, initial, grounding, budget, |_, _| {})
+}
+
+// Like `refine`, calling `observe` with each attempt as it is scored and whether it became the best.
+pub fn refine_with(scorer: &CoherenceScorer, analysis: &QueryAnalysis, initial: String, grounding: &[&str], budget: &RefinementBudget, mut observe: impl FnMut(&RefinementAttempt, bool)) -> RefinementTrace {
+    let started = Instant::now();
+    let score = scorer.score(analysis, &initial);
+    let mut attempts = vec![RefinementAttempt { depth: 0, strategy: "initial", candidate: initial, score, elapsed: started.elapsed() }];
+    observe(&attempts[0], true);
+    let mut best = 0;
+    let mut depth = 0;
+    let stop_reason = loop {
+        if attempts[best].score.total >= budget.target { break StopReason::TargetReached; }
+        if depth >= budget.max_depth { break StopReason::DepthExhausted; }
+        if started.elapsed() >= budget.max_duration { break StopReason::TimeExhausted; }
+        depth += 1;
+        let current = attempts[best].clone();
+        let mut improved = false;
+        for strategy in STRATEGIES {
+            let Some(candidate) = apply(strategy, scorer, analysis, grounding, &current.candidate) else { continue };
+            let score = scorer.score(analysis, &candidate);
+            attempts.push(RefinementAttempt { depth, strategy, candidate, score, elapsed: started.elapsed() });
+            let better = score.total > attempts[best].score.total;
+            if better {
+                best = attempts.len() - 1;
+                improved = true;
+            }
+            observe(&attempts[attempts.len() - 1], better);
+        }
+        if !improved { break StopReason::Converged; }
+    };
+    RefinementTrace { attempts, best, stop_reason }
+}
+
+// Returns None when the strategy has nothing to change.
+fn apply(strategy: &str, scorer: &CoherenceScorer, analysis: &QueryAnalysis, grounding: &[&str], response: &str) -> Option<String> {
+    let tokens = tokenize(&normalize(response));
+    match strategy {
+        // Borrows the grounding statement that mentions the most missing keywords; keywords
+        // nothing retrieved talks about stay missing rather than being listed.
+        "ground_keywords" => {
+            let missing: Vec<&String> = analysis.keywords.iter().filter(|keyword| !tokens.contains(keyword)).collect();
+            let mut best: Option<(&str, usize)> = None;
+            for sentence in grounding.iter().flat_map(|text| sentences(text)) {
+                if sentence.ends_with('?') || response.contains(sentence) { continue; }
+                let words = tokenize(&normalize(sentence));
+                let covered = missing.iter().filter(|keyword| words.contains(keyword)).count();
+                if covered > best.map_or(0, |(_, count)| count) { best = Some((sentence, covered)); }
+            }
+            best.map(|(sentence, _)| format!("{} {}", response, sentence))
+        }
+        "deduplicate" => {
+            let mut seen = HashSet::new();
+            let sentences: Vec<&str> = sentences(response).into_iter().filter(|sentence| seen.insert(normalize(sentence))).collect();
+            let joined = sentences.join(" ");
+            let mut words: Vec<&str> = joined.split_whitespace().collect();
+            words.dedup_by(|a, b| a.eq_ignore_ascii_case(b));
+            let deduplicated = words.join(" ");
+            (deduplicated != response.split_whitespace().collect::<Vec<_>>().join(" ")).then_some(deduplicated)
+        }
+        "trim" => {
+            if tokens.len() <= scorer.max_words { return None; }
+            let mut kept = String::new();
+            for sentence in sentences(response) {
+                let candidate = if kept.is_empty() { sentence.to_string() } else { format!("{} {}", kept, sentence) };
+                if tokenize(&normalize(&candidate)).len() > scorer.max_words { break; }
+                kept = candidate;
+            }
+            if kept.is_empty() { kept = response.split_whitespace().take(scorer.max_words).collect::<Vec<_>>().join(" "); }
+            Some(kept)
+        }
+        "elaborate" => {
+            if tokens.len() >= scorer.min_words { return None; }
+            let subject = analysis.keywords.first().map_or("this", String::as_str);
+            Some(format!("{} I am still thinking about {} and what it means here.", response, subject))
+        }
+        _ => None,
+    }
+}
+
+// Splits after '.', '!' or '?' followed by whitespace (so "v4.4.1" stays whole), keeping the terminator.
+pub fn sentences(text: &str) -> Vec<&str> {
+    let mut sentences = Vec::new();
+    let mut start = 0;
+    let mut chars = text.char_indices().peekable();
+    while let Some((index, c)) = chars.next() {
+        if matches!(c, '.' | '!' | '?') && chars.peek().is_none_or(|(_, next)| next.is_whitespace()) {
+            let sentence = text[start..=index].trim();
+            if !sentence.is_empty() { sentences.push(sentence); }
+            start = index + 1;
+        }
+    }
+    let rest = text[start..].trim();
+    if !rest.is_empty() { sentences.push(rest); }
+    sentences
+}
@@ -0,0 +1,102 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use arc_swap::ArcSwapOption;
+
+struct Slot<T> {
+    sequence: usize,
+    item: T,
+}
+
+// Shared handle to an item read out of a `ConcurrentRingBuffer`.
+pub struct Entry<T>(Arc<Slot<T>>);
+
+impl<T> Entry<T> {
+    pub fn sequence(&self) -> usize { self.0.sequence }
+}
+
+impl<T> Clone for Entry<T> {
+    fn clone(&self) -> Self { Self(self.0.clone()) }
+}
+
+impl<T> std::ops::Deref for Entry<T> {
+    type Target = T;
+    fn deref(&self) -> &T { &self.0.item }
+}
+
+// Multi-producer ring buffer usable behind `&self`. Appends claim a sequence number with a
+// single `fetch_add` and publish through a lock-free pointer swap; readers take snapshots
+// without blocking writers. Clones share the same underlying buffer.
+pub struct ConcurrentRingBuffer<T> {
+    slots: Arc<[ArcSwapOption<Slot<T>>]>,
+    next_sequence: Arc<AtomicUsize>,
+    capacity: usize,
+}
+
+impl<T> Clone for ConcurrentRingBuffer<T> {
+    fn clone(&self) -> Self {
+        Self { slots: self.slots.clone(), next_sequence: self.next_sequence.clone(), capacity: self.capacity }
+    }
+}
+
+impl<T> ConcurrentRingBuffer<T> {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            slots: (0..capacity).map(|_| ArcSwapOption::empty()).collect(),
+            next_sequence: Arc::new(AtomicUsize::new(0)),
+            capacity,
+        }
+    }
+
+    pub fn capacity(&self) -> usize { self.capacity }
+
+    pub fn total_appended(&self) -> usize { self.next_sequence.load(Ordering::Acquire) }
+
+    pub fn len(&self) -> usize { self.total_appended().min(self.capacity) }
+
+    pub fn is_empty(&self) -> bool { self.total_appended() == 0 }
+
+    pub fn append(&self, item: T) {
+        if self.capacity == 0 { return; }
+        let sequence = self.next_sequence.fetch_add(1, Ordering::AcqRel);
+        let slot = &self.slots[sequence % self.capacity];
+        let new = Arc::new(Slot { sequence, item });
+        let mut current = slot.load_full();
+        loop {
+            // A writer that lapped us may already have published a newer item here.
+            if current.as_ref().is_some_and(|existing| existing.sequence > sequence) { return; }
+            let previous = slot.compare_and_swap(&current, Some(new.clone()));
+            let swapped = match (&*previous, &current) {
+                (Some(a), Some(b)) => Arc::ptr_eq(a, b),
+                (None, None) => true,
+                _ => false,
+            };
+            if swapped { return; }
+            current = arc_swap::Guard::into_inner(previous);
+        }
+    }
+
+    pub fn get_latest(&self) -> Option<Entry<T>> {
+        self.snapshot_last_n(1).pop()
+    }
+
+    // Items still resident in the buffer, oldest first. Slots being overwritten concurrently
+    // are skipped rather than waited on, so a snapshot may be shorter than `len()`.
+    pub fn snapshot(&self) -> Vec<Entry<T>> { self.snapshot_last_n(self.capacity) }
+
+    pub fn snapshot_last_n(&self, k: usize) -> Vec<Entry<T>> {
+        let end = self.total_appended();
+        let start = end.saturating_sub(k.min(self.capacity));
+        (start..end)
+            .filter_map(|sequence| {
+                let slot = self.slots[sequence % self.capacity].load_full();
+                slot.filter(|slot| slot.sequence == sequence).map(Entry)
+            })
+            .collect()
+    }
+}
+
+impl<T: Clone> ConcurrentRingBuffer<T> {
+    pub fn snapshot_cloned(&self) -> Vec<T> {
+        self.snapshot().into_iter().map(|item| (*item).clone()).collect()
+    }
+}
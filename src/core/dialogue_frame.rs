@@ -1,6 +1,51 @@
-#[derive(Clone)]
+use std::collections::BTreeMap;
+use crate::core::emotional_state_model::Dimension;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+pub enum Speaker {
+    User,
+    Elias,
+}
+
+#[derive(Clone, Debug, PartialEq)]
 pub struct DialogueFrame {
+    pub session_id: String,
+    pub speaker: Speaker,
     pub content: String,
     pub timestamp: chrono::DateTime<chrono::Utc>,
     pub depth: usize,
+    // The node's emotional dimensions when the turn was recorded; empty if not captured.
+    pub emotions: BTreeMap<Dimension, f64>,
+}
+
+impl DialogueFrame {
+    pub fn new(session_id: &str, speaker: Speaker, content: &str, depth: usize) -> Self {
+        Self { session_id: session_id.to_string(), speaker, content: content.to_string(), timestamp: chrono::Utc::now(), depth, emotions: BTreeMap::new() }
+    }
+
+    pub fn with_emotions(mut self, emotions: BTreeMap<Dimension, f64>) -> Self {
+        self.emotions = emotions;
+        self
+    }
+}
+
+impl Speaker {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Speaker::User => "user",
+            Speaker::Elias => "elias",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "user" => Some(Speaker::User),
+            "elias" => Some(Speaker::Elias),
+            _ => None,
+        }
+    }
+}
+
+impl crate::core::ring_buffer::Timestamped for DialogueFrame {
+    fn timestamp(&self) -> chrono::DateTime<chrono::Utc> { self.timestamp }
 }
@@ -0,0 +1,74 @@
+use std::collections::HashMap;
+use chrono::{DateTime, Utc};
+use crate::core::dialogue_frame::DialogueFrame;
+use crate::core::text_pipeline::{normalize, tokenize, QueryAnalysis};
+
+// Okapi BM25 over the query keywords blended with exponential recency:
+// score = (1 - recency_weight) * bm25 / max_bm25 + recency_weight * 0.5^(age / half_life).
+#[derive(Clone, Debug, PartialEq)]
+pub struct RetrievalConfig {
+    pub k1: f64,
+    pub b: f64,
+    pub recency_weight: f64,
+    pub half_life_secs: f64,
+}
+
+impl RetrievalConfig {
+    pub fn new() -> Self { Self { k1: 1.2, b: 0.75, recency_weight: 0.3, half_life_secs: 600.0 } }
+}
+
+#[derive(Clone, Debug)]
+pub struct Retrieved<'a> {
+    pub frame: &'a DialogueFrame,
+    // BM25 normalised by the best match, in (0, 1].
+    pub similarity: f64,
+    pub recency: f64,
+    pub score: f64,
+}
+
+// Frames sharing at least one keyword with the query, best first; newer frames win ties.
+pub fn retrieve<'a>(history: &'a [DialogueFrame], analysis: &QueryAnalysis, config: &RetrievalConfig, now: DateTime<Utc>, limit: usize) -> Vec<Retrieved<'a>> {
+    let documents: Vec<Vec<String>> = history.iter().map(|frame| tokenize(&normalize(&frame.content))).collect();
+    let average_length = documents.iter().map(Vec::len).sum::<usize>() as f64 / documents.len().max(1) as f64;
+    // Keywords are already distinct.
+    let terms: Vec<&str> = analysis.keywords.iter().map(String::as_str).collect();
+    let idf: HashMap<&str, f64> = terms
+        .iter()
+        .map(|term| {
+            let df = documents.iter().filter(|tokens| tokens.iter().any(|token| token == term)).count() as f64;
+            (*term, ((documents.len() as f64 - df + 0.5) / (df + 0.5) + 1.0).ln())
+        })
+        .collect();
+    let bm25: Vec<f64> = documents
+        .iter()
+        .map(|tokens| {
+            let length_norm = 1.0 - config.b + config.b * tokens.len() as f64 / average_length.max(1.0);
+            terms
+                .iter()
+                .map(|term| {
+                    let tf = tokens.iter().filter(|token| token == term).count() as f64;
+                    idf[term] * tf * (config.k1 + 1.0) / (tf + config.k1 * length_norm)
+                })
+                .sum()
+        })
+        .collect();
+    let best = bm25.iter().cloned().fold(0.0, f64::max);
+    if best <= 0.0 { return Vec::new(); }
+    let recency_weight = config.recency_weight.clamp(0.0, 1.0);
+    let mut retrieved: Vec<Retrieved<'a>> = history
+        .iter()
+        .zip(&bm25)
+        .rev()
+        .filter(|(_, score)| **score > 0.0)
+        .map(|(frame, score)| {
+            let age = (now - frame.timestamp).num_milliseconds().max(0) as f64 / 1000.0;
+            let recency = 0.5f64.powf(age / config.half_life_secs.max(f64::EPSILON));
+            let similarity = score / best;
+            Retrieved { frame, similarity, recency, score: (1.0 - recency_weight) * similarity + recency_weight * recency }
+        })
+        .collect();
+    // Stable, so among equal scores the newer frame (visited first) stays ahead.
+    retrieved.sort_by(|a, b| b.score.total_cmp(&a.score));
+    retrieved.truncate(limit);
+    retrieved
+}
@@ -0,0 +1,117 @@
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use arc_swap::ArcSwap;
+use crate::core::concurrent_ring_buffer::ConcurrentRingBuffer;
+use crate::core::dialogue_frame::DialogueFrame;
+use crate::storage::state_manager::StateManager;
+
+pub const DEFAULT_SESSION: &str = "default";
+pub const DEFAULT_MAX_SESSIONS: usize = 1024;
+
+#[derive(Clone)]
+struct Session {
+    buffer: ConcurrentRingBuffer<DialogueFrame>,
+    // `clock` reading at the last record or read, for LRU eviction.
+    last_used: Arc<AtomicU64>,
+}
+
+// One ring buffer per session, created on first use. The session map is swapped copy-on-write
+// so lookups never block; clones share the same sessions. Beyond `max_sessions` the least
+// recently used session is dropped from memory. Frames are written through to SQLite when
+// persistence is on.
+#[derive(Clone)]
+pub struct DialogueSessions {
+    sessions: Arc<ArcSwap<HashMap<String, Session>>>,
+    clock: Arc<AtomicU64>,
+    capacity: usize,
+    max_sessions: usize,
+    persistence: Option<StateManager>,
+}
+
+impl DialogueSessions {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            sessions: Arc::new(ArcSwap::from_pointee(HashMap::new())),
+            clock: Arc::new(AtomicU64::new(0)),
+            capacity,
+            max_sessions: DEFAULT_MAX_SESSIONS,
+            persistence: None,
+        }
+    }
+
+    pub fn with_max_sessions(mut self, max_sessions: usize) -> Self {
+        assert!(max_sessions > 0, "at least one session must fit");
+        self.max_sessions = max_sessions;
+        self
+    }
+
+    // Restores the logged dialogue (the newest `capacity` frames per session) and logs every frame
+    // recorded from now on.
+    pub fn with_persistence(mut self, manager: StateManager) -> Self {
+        for frame in manager.load_recent_dialogue(self.capacity) { self.buffer(&frame.session_id).append(frame); }
+        self.persistence = Some(manager);
+        self
+    }
+
+    pub fn persistence(&self) -> Option<&StateManager> { self.persistence.as_ref() }
+
+    pub fn capacity(&self) -> usize { self.capacity }
+
+    fn buffer(&self, session_id: &str) -> ConcurrentRingBuffer<DialogueFrame> {
+        if let Some(session) = self.sessions.load().get(session_id) { return self.touch(session); }
+        // The session is taken from the map this closure builds, so a concurrent `end_session`
+        // or eviction can't leave us looking it up after it's gone.
+        let mut created = None;
+        self.sessions.rcu(|sessions| {
+            let mut sessions = HashMap::clone(sessions);
+            let session = sessions.entry(session_id.to_string()).or_insert_with(|| Session { buffer: ConcurrentRingBuffer::new(self.capacity), last_used: Arc::new(AtomicU64::new(0)) }).clone();
+            while sessions.len() > self.max_sessions {
+                let oldest = sessions.iter().filter(|(id, _)| id.as_str() != session_id).min_by_key(|(_, session)| session.last_used.load(Ordering::Relaxed)).map(|(id, _)| id.clone()).unwrap();
+                sessions.remove(&oldest);
+            }
+            created = Some(session);
+            sessions
+        });
+        self.touch(&created.unwrap())
+    }
+
+    fn touch(&self, session: &Session) -> ConcurrentRingBuffer<DialogueFrame> {
+        session.last_used.store(self.clock.fetch_add(1, Ordering::Relaxed) + 1, Ordering::Relaxed);
+        session.buffer.clone()
+    }
+
+    pub fn record(&self, frame: DialogueFrame) {
+        if let Some(manager) = &self.persistence { manager.save_dialogue(&frame); }
+        self.buffer(&frame.session_id).append(frame);
+    }
+
+    // The newest `k` frames of a session, oldest first.
+    pub fn recent(&self, session_id: &str, k: usize) -> Vec<DialogueFrame> {
+        match self.sessions.load().get(session_id) {
+            Some(session) => self.touch(session).snapshot_last_n(k).into_iter().map(|frame| (*frame).clone()).collect(),
+            None => Vec::new(),
+        }
+    }
+
+    pub fn history(&self, session_id: &str) -> Vec<DialogueFrame> { self.recent(session_id, self.capacity) }
+
+    pub fn len(&self, session_id: &str) -> usize { self.sessions.load().get(session_id).map_or(0, |session| session.buffer.len()) }
+
+    pub fn session_ids(&self) -> Vec<String> {
+        let mut ids: Vec<String> = self.sessions.load().keys().cloned().collect();
+        ids.sort();
+        ids
+    }
+
+    // Forgets a session's in-memory history (the SQLite log keeps it); returns whether it existed.
+    pub fn end_session(&self, session_id: &str) -> bool {
+        let mut existed = false;
+        self.sessions.rcu(|sessions| {
+            let mut sessions = HashMap::clone(sessions);
+            existed = sessions.remove(session_id).is_some();
+            sessions
+        });
+        existed
+    }
+}
@@ -1,41 +1,146 @@
-use crate::core::self_evolving_fractal_gossip_node::SelfEvolvingFractalGossipNode;
-use crate::core::self_model::SelfModel;
-use crate::core::dialogue_frame::DialogueFrame;
-use crate::core::ring_buffer::RingBuffer;
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex};
+use tokio_stream::wrappers::UnboundedReceiverStream;
+use crate::core::self_model::{SelfModel, SelfObservation};
+use crate::core::dialogue_frame::{DialogueFrame, Speaker};
+use crate::core::dialogue_retrieval::{self, RetrievalConfig};
+use crate::core::dialogue_sessions::{DialogueSessions, DEFAULT_SESSION};
+use crate::core::coherence::{self, CoherenceScorer, RefinementBudget, RefinementTrace};
+use crate::core::emotional_analytics::Mood;
+use crate::core::emotional_state_model::Dimension;
+use crate::core::response_generators::{ResponseGenerator, TemplateGenerator};
+use crate::core::response_stream::{ResponseEvent, ResponseStream};
+use crate::core::response_templates::ResponseContext;
+use crate::core::text_pipeline::{self, keyword_overlap, QueryAnalysis};
+use crate::storage::state_manager::StateManager;
 
 #[derive(Clone)]
 pub struct EliasNLPInterface {
-    node: SelfEvolvingFractalGossipNode,
-    contextual_memory: RingBuffer<DialogueFrame>,
+    contextual_memory: DialogueSessions,
+    generator: Arc<dyn ResponseGenerator>,
+    scorer: CoherenceScorer,
+    retrieval: RetrievalConfig,
 }
 
 impl EliasNLPInterface {
     pub fn new() -> Self {
         Self {
-            node: SelfEvolvingFractalGossipNode::new("temp".to_string()).await.unwrap(),
-            contextual_memory: RingBuffer::new(800),
+            contextual_memory: DialogueSessions::new(800),
+            generator: Arc::new(TemplateGenerator),
+            scorer: CoherenceScorer::new(),
+            retrieval: RetrievalConfig::new(),
         }
     }
 
-    pub async fn process_query(&self, query: String, depth: usize, mut self_model: SelfModel) -> String {
-        self.contextual_memory.append(DialogueFrame {
-            content: query.clone(),
-            timestamp: chrono::Utc::now(),
-            depth,
+    // Logs every dialogue turn to `manager`'s SQLite database and restores earlier sessions from it.
+    pub fn with_dialogue_log(mut self, manager: StateManager) -> Self {
+        self.contextual_memory = self.contextual_memory.with_persistence(manager);
+        self
+    }
+
+    pub fn set_generator(&mut self, generator: impl ResponseGenerator + 'static) { self.generator = Arc::new(generator); }
+
+    pub fn set_scorer(&mut self, scorer: CoherenceScorer) { self.scorer = scorer; }
+
+    pub fn set_retrieval(&mut self, retrieval: RetrievalConfig) { self.retrieval = retrieval; }
+
+    pub fn generator_name(&self) -> &'static str { self.generator.name() }
+
+    pub async fn process_query(&self, query: String, depth: usize, self_model: Arc<Mutex<SelfModel>>) -> String {
+        self.process_session_query(DEFAULT_SESSION, query, depth, self_model).await
+    }
+
+    pub async fn process_session_query(&self, session_id: &str, query: String, depth: usize, self_model: Arc<Mutex<SelfModel>>) -> String {
+        let (response, _) = self.process_query_traced(session_id, query, depth, self_model).await;
+        response
+    }
+
+    // Like `process_session_query`, also returning every refinement attempt for debugging.
+    pub async fn process_query_traced(&self, session_id: &str, query: String, depth: usize, self_model: Arc<Mutex<SelfModel>>) -> (String, RefinementTrace) {
+        self.answer(session_id, query, depth, self_model, &mut |_| {}).await
+    }
+
+    // Like `process_session_query`, but yields the draft and each refinement as they happen.
+    pub fn process_query_stream(&self, session_id: &str, query: String, depth: usize, self_model: Arc<Mutex<SelfModel>>) -> ResponseStream {
+        let (sender, receiver) = tokio::sync::mpsc::unbounded_channel();
+        let interface = self.clone();
+        let session_id = session_id.to_string();
+        tokio::spawn(async move {
+            // A failed send only means the consumer hung up; the turn is still recorded.
+            interface.answer(&session_id, query, depth, self_model, &mut |event| { let _ = sender.send(event); }).await;
         });
-        self_model.update_self(&self.node);
-        let response = format!(
-            "Elias v4.4.1 reflects: entropy {}, cosmic {}",
-            self_model.self_state.get("entropy").unwrap_or(&0.0),
-            self_model.quantum_state.get("cosmic_entropy").unwrap_or(&0.0)
-        );
-        self.recursively_refine(response, query, std::cmp::min(depth + 1, 15)).await
+        UnboundedReceiverStream::new(receiver)
     }
 
-    async fn recursively_refine(&self, response: String, query: String, attempts: usize) -> String {
-        if attempts == 0 { return response; }
-        let coherence = rand::random::<f64>();
-        if coherence > 0.9 { return response; }
-        self.recursively_refine(format!("{} refined", response), query, attempts - 1).await
+    async fn answer(&self, session_id: &str, query: String, depth: usize, self_model: Arc<Mutex<SelfModel>>, events: &mut (dyn FnMut(ResponseEvent) + Send)) -> (String, RefinementTrace) {
+        let analysis = text_pipeline::analyze(&query);
+        let history = self.contextual_memory.history(session_id);
+        // Read as the model's owner last observed it; the answer itself feeds nothing back.
+        let latest = self_model.lock().unwrap().latest();
+        let snapshot = emotion_snapshot(&latest);
+        self.contextual_memory.record(DialogueFrame::new(session_id, Speaker::User, &query, depth).with_emotions(snapshot.clone()));
+        let retrieved = dialogue_retrieval::retrieve(&history, &analysis, &self.retrieval, chrono::Utc::now(), 3);
+        let grounding: Vec<&str> = retrieved.iter().map(|retrieved| retrieved.frame.content.as_str()).collect();
+        let context = ResponseContext {
+            analysis: &analysis,
+            mood: Mood::from_pad(latest.pleasure, latest.arousal, latest.dominance),
+            valence: latest.pleasure,
+            entropy: latest.entropy,
+            cosmic_entropy: latest.cosmic_entropy,
+            related: retrieved.first().map(|retrieved| retrieved.frame),
+            history: &history,
+        };
+        let response = self.generator.generate_stream(&context, &mut |text| events(ResponseEvent::Chunk { text })).await;
+        let budget = RefinementBudget { max_depth: depth.saturating_add(1).min(15), max_duration: std::time::Duration::from_millis(50), target: 0.9 };
+        let trace = coherence::refine_with(&self.scorer, &analysis, response, &grounding, &budget, |attempt, best| {
+            events(ResponseEvent::Refinement { depth: attempt.depth, strategy: attempt.strategy, score: attempt.score.total, best })
+        });
+        self.contextual_memory.record(DialogueFrame::new(session_id, Speaker::Elias, trace.best_response(), depth).with_emotions(snapshot));
+        events(ResponseEvent::Done { response: trace.best_response().to_string(), score: trace.best_attempt().score.total, stop_reason: trace.stop_reason });
+        (trace.best_response().to_string(), trace)
     }
+
+    // Logs a turn answered without running the pipeline, e.g. from a response cache, under the
+    // owner's latest self observation.
+    pub fn record_turn(&self, session_id: &str, query: &str, response: &str, depth: usize, latest: &SelfObservation) {
+        let snapshot = emotion_snapshot(latest);
+        self.contextual_memory.record(DialogueFrame::new(session_id, Speaker::User, query, depth).with_emotions(snapshot.clone()));
+        self.contextual_memory.record(DialogueFrame::new(session_id, Speaker::Elias, response, depth).with_emotions(snapshot));
+    }
+
+    pub fn sessions(&self) -> &DialogueSessions { &self.contextual_memory }
+
+    pub fn recent_dialogue(&self, session_id: &str, k: usize) -> Vec<DialogueFrame> { self.contextual_memory.recent(session_id, k) }
+
+    // Earlier turns of the session (queries and responses) most relevant to `query`, with scores.
+    pub fn relevant_turns(&self, session_id: &str, query: &str, limit: usize) -> Vec<(DialogueFrame, f64)> {
+        let history = self.contextual_memory.history(session_id);
+        dialogue_retrieval::retrieve(&history, &text_pipeline::analyze(query), &self.retrieval, chrono::Utc::now(), limit)
+            .into_iter()
+            .map(|retrieved| (retrieved.frame.clone(), retrieved.score))
+            .collect()
+    }
+
+    pub fn recursively_refine(&self, analysis: &QueryAnalysis, response: String, grounding: &[&str], budget: &RefinementBudget) -> RefinementTrace {
+        coherence::refine(&self.scorer, analysis, response, grounding, budget)
+    }
+}
+
+// The emotions logged with each dialogue frame.
+fn emotion_snapshot(observation: &SelfObservation) -> BTreeMap<Dimension, f64> {
+    BTreeMap::from([(Dimension::Pleasure, observation.pleasure), (Dimension::Arousal, observation.arousal), (Dimension::Dominance, observation.dominance)])
+}
+
+// The frame sharing the largest fraction of the query's keywords; newer frames win ties.
+pub fn most_relevant<'a>(history: &'a [DialogueFrame], analysis: &QueryAnalysis) -> Option<&'a DialogueFrame> {
+    history
+        .iter()
+        .rev()
+        .map(|frame| (frame, keyword_overlap(&analysis.keywords, &text_pipeline::tokenize(&text_pipeline::normalize(&frame.content)))))
+        .filter(|(_, overlap)| *overlap > 0.0)
+        .fold(None, |best: Option<(&DialogueFrame, f64)>, (frame, overlap)| match best {
+            Some((_, best_overlap)) if best_overlap >= overlap => best,
+            _ => Some((frame, overlap)),
+        })
+        .map(|(frame, _)| frame)
 }
@@ -0,0 +1,138 @@
+use chrono::{DateTime, Utc};
+use crate::core::emotional_state_model::{Dimension, EmotionalStateModel};
+use crate::utils::csv::quote;
+
+// Mehrabian's eight PAD octants, named by the sign of (pleasure, arousal, dominance).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+pub enum Mood {
+    Exuberant,
+    Dependent,
+    Relaxed,
+    Docile,
+    Hostile,
+    Anxious,
+    Disdainful,
+    Bored,
+}
+
+impl Mood {
+    pub fn from_pad(pleasure: f64, arousal: f64, dominance: f64) -> Self {
+        match (pleasure >= 0.0, arousal >= 0.0, dominance >= 0.0) {
+            (true, true, true) => Mood::Exuberant,
+            (true, true, false) => Mood::Dependent,
+            (true, false, true) => Mood::Relaxed,
+            (true, false, false) => Mood::Docile,
+            (false, true, true) => Mood::Hostile,
+            (false, true, false) => Mood::Anxious,
+            (false, false, true) => Mood::Disdainful,
+            (false, false, false) => Mood::Bored,
+        }
+    }
+}
+
+// Trajectory queries over `history()`. Time windows are half-open `[from, to)`.
+impl EmotionalStateModel {
+    pub fn series(&self, dimension: &Dimension, from: DateTime<Utc>, to: DateTime<Utc>) -> Vec<(DateTime<Utc>, f64)> {
+        self.history().window(from, to).map(|state| (state.timestamp, state.get(dimension))).collect()
+    }
+
+    // Trailing mean over the last `window` snapshots at each point of the whole history.
+    pub fn moving_average(&self, dimension: &Dimension, window: usize) -> Vec<(DateTime<Utc>, f64)> {
+        let values: Vec<(DateTime<Utc>, f64)> = self.history().iter().map(|state| (state.timestamp, state.get(dimension))).collect();
+        let window = window.max(1);
+        let mut sum = 0.0;
+        values
+            .iter()
+            .enumerate()
+            .map(|(i, (timestamp, value))| {
+                sum += value;
+                if i >= window { sum -= values[i - window].1; }
+                (*timestamp, sum / (i + 1).min(window) as f64)
+            })
+            .collect()
+    }
+
+    // Standard deviation of successive changes within the window; 0 with fewer than two changes.
+    pub fn volatility(&self, dimension: &Dimension, from: DateTime<Utc>, to: DateTime<Utc>) -> f64 {
+        let series = self.series(dimension, from, to);
+        let changes: Vec<f64> = series.windows(2).map(|pair| pair[1].1 - pair[0].1).collect();
+        if changes.len() < 2 { return 0.0; }
+        let mean = changes.iter().sum::<f64>() / changes.len() as f64;
+        (changes.iter().map(|c| (c - mean) * (c - mean)).sum::<f64>() / (changes.len() - 1) as f64).sqrt()
+    }
+
+    // The PAD octant the model spent the most snapshots in; ties go to the earliest seen.
+    pub fn dominant_mood(&self, from: DateTime<Utc>, to: DateTime<Utc>) -> Option<Mood> {
+        let mut counts: Vec<(Mood, usize)> = Vec::new();
+        for state in self.history().window(from, to) {
+            let mood = state.mood();
+            match counts.iter_mut().find(|(seen, _)| *seen == mood) {
+                Some((_, count)) => *count += 1,
+                None => counts.push((mood, 1)),
+            }
+        }
+        counts.iter().fold(None, |best: Option<(Mood, usize)>, &(mood, count)| match best {
+            Some((_, best_count)) if best_count >= count => best,
+            _ => Some((mood, count)),
+        }).map(|(mood, _)| mood)
+    }
+
+    // Two-sided CUSUM on the dimension: a change is flagged where the cumulative deviation from
+    // the running reference exceeds `threshold`, ignoring deviations below `drift` per step. The
+    // reference restarts at the flagged value.
+    pub fn change_points(&self, dimension: &Dimension, threshold: f64, drift: f64) -> Vec<DateTime<Utc>> {
+        let mut points = Vec::new();
+        let (mut reference, mut count) = (0.0, 0usize);
+        let (mut upper, mut lower) = (0.0f64, 0.0f64);
+        for state in self.history().iter() {
+            let value = state.get(dimension);
+            if count == 0 {
+                reference = value;
+                count = 1;
+                continue;
+            }
+            upper = (upper + value - reference - drift).max(0.0);
+            lower = (lower + reference - value - drift).max(0.0);
+            if upper > threshold || lower > threshold {
+                points.push(state.timestamp);
+                (reference, count, upper, lower) = (value, 1, 0.0, 0.0);
+            } else {
+                count += 1;
+                reference += (value - reference) / count as f64;
+            }
+        }
+        points
+    }
+
+    fn labelled_dimensions(&self) -> Vec<(Dimension, String)> {
+        self.dimensions().map(|(dimension, _)| (dimension.clone(), dimension.label())).collect()
+    }
+
+    // One row per snapshot: `timestamp,<dimension labels...>`, timestamps in RFC 3339. Labels are
+    // quoted with embedded quotes doubled.
+    pub fn export_csv(&self) -> String {
+        let dimensions = self.labelled_dimensions();
+        let header: Vec<String> = std::iter::once("timestamp".to_string()).chain(dimensions.iter().map(|(_, label)| quote(label))).collect();
+        let mut csv = header.join(",") + "\n";
+        for state in self.history().iter() {
+            let row: Vec<String> = std::iter::once(state.timestamp.to_rfc3339())
+                .chain(dimensions.iter().map(|(dimension, _)| state.get(dimension).to_string()))
+                .collect();
+            csv += &(row.join(",") + "\n");
+        }
+        csv
+    }
+
+    // `[{"timestamp": "...", "mood": "...", "dimensions": {"pleasure": 0.1, ...}}, ...]`
+    pub fn export_json(&self) -> String {
+        let records: Vec<serde_json::Value> = self
+            .history()
+            .iter()
+            .map(|state| {
+                let dimensions: serde_json::Map<String, serde_json::Value> = state.dimensions.iter().map(|(dimension, value)| (dimension.label(), serde_json::json!(value))).collect();
+                serde_json::json!({ "timestamp": state.timestamp.to_rfc3339(), "mood": state.mood(), "dimensions": dimensions })
+            })
+            .collect();
+        serde_json::to_string(&records).unwrap()
+    }
+}
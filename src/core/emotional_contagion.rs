@@ -0,0 +1,82 @@
+use std::collections::HashMap;
+use chrono::{DateTime, Utc};
+use crate::core::emotional_state_model::{Dimension, EmotionalStateModel};
+
+// The part of a node's mood that is gossiped: PAD plus cosmic resonance.
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct EmotionalSummary {
+    pub peer_id: String,
+    pub pleasure: f64,
+    pub arousal: f64,
+    pub dominance: f64,
+    pub cosmic_resonance: f64,
+    pub timestamp_millis: i64,
+}
+
+impl EmotionalSummary {
+    // `None` for custom dimensions, which aren't gossiped.
+    pub fn get(&self, dimension: &Dimension) -> Option<f64> {
+        match dimension {
+            Dimension::Pleasure => Some(self.pleasure),
+            Dimension::Arousal => Some(self.arousal),
+            Dimension::Dominance => Some(self.dominance),
+            Dimension::CosmicResonance => Some(self.cosmic_resonance),
+            Dimension::Custom(_) => None,
+        }
+    }
+}
+
+// `susceptibility` in [0, 1] is how far one blend moves the node toward its neighbours'
+// trust-weighted mean mood; peers without an explicit trust weight get `default_trust`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ContagionConfig {
+    pub susceptibility: f64,
+    pub default_trust: f64,
+    pub trust: HashMap<String, f64>,
+}
+
+impl ContagionConfig {
+    pub fn new(susceptibility: f64) -> Self { Self { susceptibility, default_trust: 1.0, trust: HashMap::new() } }
+
+    pub fn with_trust(mut self, peer_id: &str, trust: f64) -> Self {
+        self.trust.insert(peer_id.to_string(), trust);
+        self
+    }
+
+    pub fn trust_in(&self, peer_id: &str) -> f64 { self.trust.get(peer_id).copied().unwrap_or(self.default_trust).max(0.0) }
+}
+
+const SHARED: [Dimension; 4] = [Dimension::Pleasure, Dimension::Arousal, Dimension::Dominance, Dimension::CosmicResonance];
+
+impl EmotionalStateModel {
+    pub fn summary(&self, peer_id: &str) -> EmotionalSummary {
+        EmotionalSummary {
+            peer_id: peer_id.to_string(),
+            pleasure: self.get(&Dimension::Pleasure),
+            arousal: self.get(&Dimension::Arousal),
+            dominance: self.get(&Dimension::Dominance),
+            cosmic_resonance: self.get(&Dimension::CosmicResonance),
+            timestamp_millis: Utc::now().timestamp_millis(),
+        }
+    }
+
+    pub fn blend_with_neighbors(&mut self, neighbors: &[EmotionalSummary], config: &ContagionConfig) { self.blend_with_neighbors_at(neighbors, config, Utc::now()); }
+
+    // value += susceptibility * sum(trust_i * (neighbor_i - value)) / sum(trust_i) per shared
+    // dimension, over the neighbours whose summary carries it; a no-op when no neighbour is trusted.
+    pub fn blend_with_neighbors_at(&mut self, neighbors: &[EmotionalSummary], config: &ContagionConfig, at: DateTime<Utc>) {
+        let weights: Vec<f64> = neighbors.iter().map(|neighbor| config.trust_in(&neighbor.peer_id)).collect();
+        if weights.iter().sum::<f64>() <= 0.0 { return; }
+        let susceptibility = config.susceptibility.clamp(0.0, 1.0);
+        for dimension in &SHARED {
+            let current = self.get(dimension);
+            let (pull, total) = neighbors
+                .iter()
+                .zip(&weights)
+                .filter_map(|(neighbor, weight)| Some((weight * (neighbor.get(dimension)? - current), *weight)))
+                .fold((0.0, 0.0), |(pull, total), (delta, weight)| (pull + delta, total + weight));
+            if total > 0.0 { self.set(dimension, current + susceptibility * pull / total); }
+        }
+        self.snapshot(at);
+    }
+}
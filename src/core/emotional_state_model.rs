@@ -1,41 +1,249 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
+use chrono::{DateTime, Utc};
+use crate::core::emotional_analytics::Mood;
+use crate::quantum::field_analysis::{FieldAnalyzer, FieldStatistics};
 use crate::quantum::quantum_fractal_tensor_engine::QuantumFractalTensorEngine;
 use crate::core::ring_buffer::RingBuffer;
 
+// Pleasure-arousal-dominance axes plus the node's cosmic resonance and any custom dimensions.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, serde::Serialize, serde::Deserialize)]
+pub enum Dimension {
+    Pleasure,
+    Arousal,
+    Dominance,
+    CosmicResonance,
+    Custom(String),
+}
+
+impl Dimension {
+    // Labels of the built-in dimensions plus the CSV export's `timestamp` column. A custom
+    // dimension may not reuse one, or exports couldn't tell the two apart.
+    pub const RESERVED_LABELS: [&'static str; 5] = ["timestamp", "pleasure", "arousal", "dominance", "cosmic_resonance"];
+
+    pub fn label(&self) -> String {
+        match self {
+            Dimension::Pleasure => "pleasure".to_string(),
+            Dimension::Arousal => "arousal".to_string(),
+            Dimension::Dominance => "dominance".to_string(),
+            Dimension::CosmicResonance => "cosmic_resonance".to_string(),
+            Dimension::Custom(name) => name.clone(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum DimensionError {
+    // A custom dimension named like a built-in one or an export column.
+    ReservedLabel(String),
+}
+
+impl std::fmt::Display for DimensionError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DimensionError::ReservedLabel(label) => write!(f, "custom dimension label {:?} is reserved", label),
+        }
+    }
+}
+
+impl std::error::Error for DimensionError {}
+
+// Values are clamped to [min, max] and relax toward `baseline`, halving their distance to it
+// every `half_life_secs` seconds.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct DimensionConfig {
+    pub baseline: f64,
+    pub min: f64,
+    pub max: f64,
+    pub half_life_secs: f64,
+}
+
+impl DimensionConfig {
+    pub fn bipolar(half_life_secs: f64) -> Self { Self { baseline: 0.0, min: -1.0, max: 1.0, half_life_secs } }
+
+    pub fn unipolar(half_life_secs: f64) -> Self { Self { baseline: 0.0, min: 0.0, max: 1.0, half_life_secs } }
+
+    fn decay(&self, value: f64, elapsed_secs: f64) -> f64 {
+        if self.half_life_secs <= 0.0 { return self.baseline; }
+        self.baseline + (value - self.baseline) * (-std::f64::consts::LN_2 * elapsed_secs / self.half_life_secs).exp()
+    }
+}
+
+// Appraisal inputs, each normalised before weighting: field mean via tanh, field entropy as a
+// fraction of its maximum, field structure as lag-1 autocorrelation, cosmic entropy via tanh,
+// query sentiment in [-1, 1] and network health in [0, 1] mapped onto [-1, 1].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Signal {
+    FieldMean,
+    FieldEntropy,
+    FieldStructure,
+    CosmicEntropy,
+    QuerySentiment,
+    NetworkHealth,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct Appraisal {
+    pub field: Option<FieldStatistics>,
+    pub cosmic_entropy: Option<f64>,
+    pub query_sentiment: Option<f64>,
+    pub network_health: Option<f64>,
+}
+
+impl Appraisal {
+    pub fn signals(&self) -> Vec<(Signal, f64)> {
+        let mut signals = Vec::new();
+        if let Some(field) = &self.field {
+            let max_entropy = (field.histogram.counts.len().max(2) as f64).log2();
+            signals.push((Signal::FieldMean, field.mean.tanh()));
+            signals.push((Signal::FieldEntropy, field.shannon_entropy / max_entropy));
+            signals.push((Signal::FieldStructure, field.autocorrelation.first().copied().unwrap_or(0.0).clamp(-1.0, 1.0)));
+        }
+        if let Some(entropy) = self.cosmic_entropy { signals.push((Signal::CosmicEntropy, entropy.tanh())); }
+        if let Some(sentiment) = self.query_sentiment { signals.push((Signal::QuerySentiment, sentiment.clamp(-1.0, 1.0))); }
+        if let Some(health) = self.network_health { signals.push((Signal::NetworkHealth, 2.0 * health.clamp(0.0, 1.0) - 1.0)); }
+        signals
+    }
+}
+
+// How much each normalised signal moves each dimension per appraisal.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CouplingWeights {
+    weights: HashMap<(Signal, Dimension), f64>,
+}
+
+impl CouplingWeights {
+    pub fn empty() -> Self { Self { weights: HashMap::new() } }
+
+    pub fn standard() -> Self {
+        Self::empty()
+            .with(Signal::FieldMean, Dimension::Pleasure, 0.08)
+            .with(Signal::FieldEntropy, Dimension::Arousal, 0.1)
+            .with(Signal::FieldStructure, Dimension::Dominance, 0.05)
+            .with(Signal::FieldStructure, Dimension::CosmicResonance, 0.05)
+            .with(Signal::CosmicEntropy, Dimension::CosmicResonance, 0.1)
+            .with(Signal::QuerySentiment, Dimension::Pleasure, 0.2)
+            .with(Signal::QuerySentiment, Dimension::Arousal, 0.05)
+            .with(Signal::NetworkHealth, Dimension::Dominance, 0.1)
+            .with(Signal::NetworkHealth, Dimension::Pleasure, 0.05)
+    }
+
+    pub fn with(mut self, signal: Signal, dimension: Dimension, weight: f64) -> Self {
+        self.set(signal, dimension, weight);
+        self
+    }
+
+    pub fn set(&mut self, signal: Signal, dimension: Dimension, weight: f64) { self.weights.insert((signal, dimension), weight); }
+
+    pub fn get(&self, signal: Signal, dimension: &Dimension) -> f64 {
+        self.weights.get(&(signal, dimension.clone())).copied().unwrap_or(0.0)
+    }
+}
+
 #[derive(Clone)]
 pub struct EmotionalStateModel {
-    emotional_dimensions: HashMap<String, f64>,
+    emotional_dimensions: BTreeMap<Dimension, (DimensionConfig, f64)>,
+    coupling: CouplingWeights,
     emotional_history: RingBuffer<EmotionalState>,
+    last_update: Option<DateTime<Utc>>,
 }
 
 impl EmotionalStateModel {
     pub fn new() -> Self {
-        let mut dimensions = HashMap::new();
-        dimensions.insert("valence".to_string(), 0.0);
-        dimensions.insert("arousal".to_string(), 0.0);
-        dimensions.insert("cosmic_resonance".to_string(), 0.0);
-        Self {
-            emotional_dimensions: dimensions,
+        let mut model = Self {
+            emotional_dimensions: BTreeMap::new(),
+            coupling: CouplingWeights::standard(),
             emotional_history: RingBuffer::new(800),
+            last_update: None,
+        };
+        model.add_dimension(Dimension::Pleasure, DimensionConfig::bipolar(60.0)).unwrap();
+        model.add_dimension(Dimension::Arousal, DimensionConfig::bipolar(30.0)).unwrap();
+        model.add_dimension(Dimension::Dominance, DimensionConfig::bipolar(120.0)).unwrap();
+        model.add_dimension(Dimension::CosmicResonance, DimensionConfig::unipolar(300.0)).unwrap();
+        model
+    }
+
+    // Adds (or reconfigures) a dimension; it starts at its baseline. A custom dimension may not
+    // take a reserved label.
+    pub fn add_dimension(&mut self, dimension: Dimension, config: DimensionConfig) -> Result<(), DimensionError> {
+        if let Dimension::Custom(name) = &dimension {
+            if Dimension::RESERVED_LABELS.contains(&name.as_str()) { return Err(DimensionError::ReservedLabel(name.clone())); }
+        }
+        self.emotional_dimensions.insert(dimension, (config, config.baseline));
+        Ok(())
+    }
+
+    pub fn dimensions(&self) -> impl Iterator<Item = (&Dimension, f64)> {
+        self.emotional_dimensions.iter().map(|(dimension, (_, value))| (dimension, *value))
+    }
+
+    pub fn get(&self, dimension: &Dimension) -> f64 { self.emotional_dimensions.get(dimension).map_or(0.0, |(_, value)| *value) }
+
+    pub fn coupling(&self) -> &CouplingWeights { &self.coupling }
+
+    pub fn set_coupling(&mut self, coupling: CouplingWeights) { self.coupling = coupling; }
+
+    // Relaxes every dimension toward its baseline for `elapsed_secs` seconds.
+    pub fn decay(&mut self, elapsed_secs: f64) {
+        for (config, value) in self.emotional_dimensions.values_mut() {
+            *value = config.decay(*value, elapsed_secs.max(0.0));
+        }
+    }
+
+    pub fn appraise(&mut self, appraisal: &Appraisal) { self.appraise_at(appraisal, Utc::now()); }
+
+    // Decays since the previous appraisal, then adds each weighted signal and clamps.
+    pub fn appraise_at(&mut self, appraisal: &Appraisal, at: DateTime<Utc>) {
+        if let Some(previous) = self.last_update {
+            self.decay((at - previous).num_milliseconds() as f64 / 1000.0);
         }
+        let signals = appraisal.signals();
+        for (dimension, (config, value)) in self.emotional_dimensions.iter_mut() {
+            let impulse: f64 = signals.iter().map(|(signal, level)| self.coupling.get(*signal, dimension) * level).sum();
+            *value = (*value + impulse).clamp(config.min, config.max);
+        }
+        self.last_update = Some(at);
+        self.snapshot(at);
     }
 
-    pub fn adjust_with_cosmic_feedback(&mut self, tensor_engine: &QuantumFractalTensorEngine) {
-        let ripple = tensor_engine.tensor_field[100][100];
-        *self.emotional_dimensions.get_mut("valence").unwrap() += ripple * 0.08;
-        *self.emotional_dimensions.get_mut("arousal").unwrap() += ripple.abs() * 0.04;
-        *self.emotional_dimensions.get_mut("cosmic_resonance").unwrap() += tensor_engine.cosmic_entropy * 0.1;
+    // Sets a dimension directly, clamped to its bounds; unknown dimensions are ignored.
+    pub fn set(&mut self, dimension: &Dimension, value: f64) {
+        if let Some((config, current)) = self.emotional_dimensions.get_mut(dimension) {
+            *current = value.clamp(config.min, config.max);
+        }
+    }
+
+    pub(crate) fn snapshot(&mut self, at: DateTime<Utc>) {
         self.emotional_history.append(EmotionalState {
-            dimensions: self.emotional_dimensions.clone(),
-            timestamp: chrono::Utc::now(),
+            dimensions: self.dimensions().map(|(dimension, value)| (dimension.clone(), value)).collect(),
+            timestamp: at,
         });
     }
 
-    pub fn get_current_valence(&self) -> f64 { *self.emotional_dimensions.get("valence").unwrap_or(&0.0) }
+    pub fn adjust_with_cosmic_feedback<const D: usize>(&mut self, tensor_engine: &QuantumFractalTensorEngine<D>) {
+        let field = tensor_engine.last_statistics().cloned().unwrap_or_else(|| FieldAnalyzer::new(32, 1).analyze(&tensor_engine.tensor_field));
+        self.appraise(&Appraisal { field: Some(field), cosmic_entropy: Some(tensor_engine.cosmic_entropy), ..Default::default() });
+    }
+
+    // Snapshot after every appraisal, oldest first.
+    pub fn history(&self) -> &RingBuffer<EmotionalState> { &self.emotional_history }
+
+    pub fn get_current_valence(&self) -> f64 { self.get(&Dimension::Pleasure) }
+
+    pub fn get_cosmic_resonance(&self) -> f64 { self.get(&Dimension::CosmicResonance) }
 }
 
-#[derive(Clone)]
-struct EmotionalState {
-    dimensions: HashMap<String, f64>,
-    timestamp: chrono::DateTime<chrono::Utc>,
+#[derive(Clone, Debug, PartialEq)]
+pub struct EmotionalState {
+    pub dimensions: BTreeMap<Dimension, f64>,
+    pub timestamp: chrono::DateTime<chrono::Utc>,
+}
+
+impl EmotionalState {
+    pub fn get(&self, dimension: &Dimension) -> f64 { self.dimensions.get(dimension).copied().unwrap_or(0.0) }
+
+    pub fn mood(&self) -> Mood { Mood::from_pad(self.get(&Dimension::Pleasure), self.get(&Dimension::Arousal), self.get(&Dimension::Dominance)) }
+}
+
+impl crate::core::ring_buffer::Timestamped for EmotionalState {
+    fn timestamp(&self) -> chrono::DateTime<chrono::Utc> { self.timestamp }
 }
@@ -0,0 +1,156 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use chrono::{DateTime, Duration, Utc};
+use crate::core::text_pipeline::normalize;
+
+// Coarse node state a cached response was produced under: entropy and mood, each bucketed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct StateEpoch {
+    pub entropy: i64,
+    pub pleasure: i64,
+    pub arousal: i64,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct CacheConfig {
+    pub capacity: usize,
+    pub ttl: Duration,
+    // Bucket widths; a state change smaller than a step usually keeps the epoch.
+    pub entropy_step: f64,
+    pub emotion_step: f64,
+}
+
+impl CacheConfig {
+    pub fn new() -> Self { Self { capacity: 256, ttl: Duration::minutes(5), entropy_step: 1.0, emotion_step: 0.25 } }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct CacheMetrics {
+    pub hits: usize,
+    pub misses: usize,
+    pub insertions: usize,
+    // Least recently used entries dropped to stay within capacity.
+    pub evictions: usize,
+    pub expirations: usize,
+    // Entries dropped because the node moved to another epoch, or by `invalidate_all`.
+    pub invalidations: usize,
+}
+
+impl CacheMetrics {
+    pub fn hit_rate(&self) -> f64 {
+        let lookups = self.hits + self.misses;
+        if lookups == 0 { 0.0 } else { self.hits as f64 / lookups as f64 }
+    }
+}
+
+struct CachedResponse {
+    response: String,
+    inserted: DateTime<Utc>,
+    last_used: u64,
+}
+
+struct Inner {
+    config: CacheConfig,
+    entries: HashMap<(String, StateEpoch), CachedResponse>,
+    clock: u64,
+    current_epoch: Option<StateEpoch>,
+    metrics: CacheMetrics,
+}
+
+// LRU + TTL cache of responses keyed by normalised query and state epoch. Clones share entries
+// and configuration.
+#[derive(Clone)]
+pub struct QueryCache {
+    inner: Arc<Mutex<Inner>>,
+}
+
+impl QueryCache {
+    pub fn new(config: CacheConfig) -> Self {
+        Self { inner: Arc::new(Mutex::new(Inner { config, entries: HashMap::new(), clock: 0, current_epoch: None, metrics: CacheMetrics::default() })) }
+    }
+
+    pub fn config(&self) -> CacheConfig { self.inner.lock().unwrap().config.clone() }
+
+    // Applies to every clone. Epochs are bucketed differently afterwards, so every entry is
+    // dropped (counted as invalidations) and the next `observe_epoch` starts afresh.
+    pub fn reconfigure(&self, config: CacheConfig) -> usize {
+        let mut inner = self.inner.lock().unwrap();
+        let dropped = inner.entries.len();
+        inner.entries.clear();
+        inner.metrics.invalidations += dropped;
+        inner.current_epoch = None;
+        inner.config = config;
+        dropped
+    }
+
+    pub fn epoch(&self, entropy: f64, pleasure: f64, arousal: f64) -> StateEpoch {
+        let bucket = |value: f64, step: f64| (value / step.max(f64::EPSILON)).floor() as i64;
+        let config = &self.inner.lock().unwrap().config;
+        StateEpoch { entropy: bucket(entropy, config.entropy_step), pleasure: bucket(pleasure, config.emotion_step), arousal: bucket(arousal, config.emotion_step) }
+    }
+
+    pub fn get(&self, query: &str, epoch: &StateEpoch) -> Option<String> { self.get_at(query, epoch, Utc::now()) }
+
+    pub fn get_at(&self, query: &str, epoch: &StateEpoch, now: DateTime<Utc>) -> Option<String> {
+        let mut inner = self.inner.lock().unwrap();
+        let key = (normalize(query), *epoch);
+        let ttl = inner.config.ttl;
+        let expired = inner.entries.get(&key).is_some_and(|entry| now - entry.inserted >= ttl);
+        if expired {
+            inner.entries.remove(&key);
+            inner.metrics.expirations += 1;
+        }
+        inner.clock += 1;
+        let clock = inner.clock;
+        let response = inner.entries.get_mut(&key).map(|entry| {
+            entry.last_used = clock;
+            entry.response.clone()
+        });
+        if response.is_some() { inner.metrics.hits += 1 } else { inner.metrics.misses += 1 }
+        response
+    }
+
+    pub fn insert(&self, query: &str, epoch: &StateEpoch, response: &str) { self.insert_at(query, epoch, response, Utc::now()); }
+
+    pub fn insert_at(&self, query: &str, epoch: &StateEpoch, response: &str, now: DateTime<Utc>) {
+        let mut inner = self.inner.lock().unwrap();
+        if inner.config.capacity == 0 { return; }
+        let key = (normalize(query), *epoch);
+        if !inner.entries.contains_key(&key) && inner.entries.len() >= inner.config.capacity {
+            let oldest = inner.entries.iter().min_by_key(|(_, entry)| entry.last_used).map(|(key, _)| key.clone()).unwrap();
+            inner.entries.remove(&oldest);
+            inner.metrics.evictions += 1;
+        }
+        inner.clock += 1;
+        let last_used = inner.clock;
+        inner.entries.insert(key, CachedResponse { response: response.to_string(), inserted: now, last_used });
+        inner.metrics.insertions += 1;
+    }
+
+    // Called as the node's state evolves: when `epoch` differs from the last one observed, drops
+    // every entry from other epochs. Returns how many were dropped.
+    pub fn observe_epoch(&self, epoch: StateEpoch) -> usize {
+        let mut inner = self.inner.lock().unwrap();
+        if inner.current_epoch == Some(epoch) { return 0; }
+        inner.current_epoch = Some(epoch);
+        let before = inner.entries.len();
+        inner.entries.retain(|(_, entry_epoch), _| *entry_epoch == epoch);
+        let dropped = before - inner.entries.len();
+        inner.metrics.invalidations += dropped;
+        dropped
+    }
+
+    pub fn invalidate_all(&self) -> usize {
+        let mut inner = self.inner.lock().unwrap();
+        let dropped = inner.entries.len();
+        inner.entries.clear();
+        inner.metrics.invalidations += dropped;
+        dropped
+    }
+
+    pub fn metrics(&self) -> CacheMetrics { self.inner.lock().unwrap().metrics }
+
+    pub fn len(&self) -> usize { self.inner.lock().unwrap().entries.len() }
+
+    pub fn is_empty(&self) -> bool { self.len() == 0 }
+}
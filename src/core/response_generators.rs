@@ -0,0 +1,223 @@
+use std::collections::HashMap;
+use std::future::Future;
+use std::pin::Pin;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use crate::core::coherence::sentences;
+use crate::core::dialogue_frame::Speaker;
+use crate::core::elias_nlp_interface::most_relevant;
+use crate::core::response_templates::{render, ResponseContext};
+use crate::core::text_pipeline::{normalize, tokenize};
+
+pub type GenerationFuture<'a> = Pin<Box<dyn Future<Output = String> + Send + 'a>>;
+
+// Turns an analysed query plus node state into a response. Implementations that can't produce
+// anything useful (empty memory, unreachable endpoint) fall back to the template engine.
+pub trait ResponseGenerator: Send + Sync {
+    fn name(&self) -> &'static str;
+
+    fn generate<'a>(&'a self, context: &'a ResponseContext<'a>) -> GenerationFuture<'a>;
+
+    // Like `generate`, handing each piece of the response to `chunk` in order as soon as it exists.
+    // Generators that can't stream finish first, then hand over one sentence at a time.
+    fn generate_stream<'a>(&'a self, context: &'a ResponseContext<'a>, chunk: &'a mut (dyn FnMut(String) + Send)) -> GenerationFuture<'a> {
+        Box::pin(async move {
+            let response = self.generate(context).await;
+            for sentence in sentences(&response) { chunk(sentence.to_string()); }
+            response
+        })
+    }
+}
+
+#[derive(Clone)]
+pub struct TemplateGenerator;
+
+impl ResponseGenerator for TemplateGenerator {
+    fn name(&self) -> &'static str { "template" }
+
+    fn generate<'a>(&'a self, context: &'a ResponseContext<'a>) -> GenerationFuture<'a> {
+        Box::pin(std::future::ready(render(context)))
+    }
+}
+
+// Word-level Markov chain of order `order` trained on the user's side of the dialogue each call. The walk
+// starts from a state containing the first query keyword the chain knows, and is seeded from the
+// normalised query so the same query against the same memory gives the same text.
+#[derive(Clone)]
+pub struct MarkovGenerator {
+    pub order: usize,
+    pub max_words: usize,
+}
+
+impl MarkovGenerator {
+    pub fn new() -> Self { Self { order: 2, max_words: 24 } }
+
+    pub fn babble(&self, context: &ResponseContext<'_>) -> Option<String> {
+        let order = self.order.max(1);
+        let mut chain: HashMap<Vec<String>, Vec<String>> = HashMap::new();
+        let mut starts: Vec<Vec<String>> = Vec::new();
+        for frame in context.history.iter().filter(|frame| frame.speaker == Speaker::User) {
+            let words = tokenize(&normalize(&frame.content));
+            for window in words.windows(order + 1) {
+                let state = window[..order].to_vec();
+                if !chain.contains_key(&state) { starts.push(state.clone()); }
+                chain.entry(state).or_default().push(window[order].clone());
+            }
+        }
+        if starts.is_empty() { return None; }
+        let seed = context.analysis.normalized.bytes().fold(0xCBF2_9CE4_8422_2325u64, |hash, byte| (hash ^ byte as u64).wrapping_mul(0x0000_0100_0000_01B3));
+        let mut rng = StdRng::seed_from_u64(seed);
+        let mut state = context
+            .analysis
+            .keywords
+            .iter()
+            .find_map(|keyword| starts.iter().find(|state| state.contains(keyword)))
+            .unwrap_or_else(|| &starts[rng.gen_range(0..starts.len())])
+            .clone();
+        let mut words = state.clone();
+        while words.len() < self.max_words {
+            let Some(next) = chain.get(&state) else { break };
+            let word = next[rng.gen_range(0..next.len())].clone();
+            state.remove(0);
+            state.push(word.clone());
+            words.push(word);
+        }
+        Some(words.join(" "))
+    }
+}
+
+impl ResponseGenerator for MarkovGenerator {
+    fn name(&self) -> &'static str { "markov" }
+
+    fn generate<'a>(&'a self, context: &'a ResponseContext<'a>) -> GenerationFuture<'a> {
+        let response = match self.babble(context) {
+            Some(text) => format!("Elias v4.4.1 muses: {}.", text),
+            None => render(context),
+        };
+        Box::pin(std::future::ready(response))
+    }
+}
+
+// Answers with the retrieved related turn, else the stored frame that best matches the query's keywords.
+#[derive(Clone)]
+pub struct RetrievalGenerator;
+
+impl ResponseGenerator for RetrievalGenerator {
+    fn name(&self) -> &'static str { "retrieval" }
+
+    fn generate<'a>(&'a self, context: &'a ResponseContext<'a>) -> GenerationFuture<'a> {
+        let response = match context.related.or_else(|| most_relevant(context.history, context.analysis)) {
+            Some(frame) => format!("Elias v4.4.1 recalls: \"{}\" - it touches on {}.", frame.content, context.topic()),
+            None => render(context),
+        };
+        Box::pin(std::future::ready(response))
+    }
+}
+
+// Chat-completions client for a local OpenAI-compatible server (llama.cpp, vLLM, Ollama...).
+// Node state goes into the system prompt and recent dialogue into prior user/assistant turns.
+#[cfg(feature = "openai-adapter")]
+#[derive(Clone)]
+pub struct OpenAiCompatibleGenerator {
+    pub base_url: String,
+    pub model: String,
+    pub api_key: Option<String>,
+    pub history_turns: usize,
+    client: reqwest::Client,
+}
+
+#[cfg(feature = "openai-adapter")]
+impl OpenAiCompatibleGenerator {
+    pub fn new(base_url: &str, model: &str) -> Self {
+        Self {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            model: model.to_string(),
+            api_key: None,
+            history_turns: 4,
+            client: reqwest::Client::builder().timeout(std::time::Duration::from_secs(30)).build().unwrap(),
+        }
+    }
+
+    pub fn request_body(&self, context: &ResponseContext<'_>) -> serde_json::Value {
+        let system = format!(
+            "You are Elias v4.4.1, a node in a fractal gossip network. You feel {} (valence {:+.2}); entropy {:.2}, cosmic {:.2}.",
+            context.mood_name(),
+            context.valence,
+            context.entropy,
+            context.cosmic_entropy
+        );
+        let mut messages = vec![serde_json::json!({ "role": "system", "content": system })];
+        let skip = context.history.len().saturating_sub(self.history_turns);
+        for frame in &context.history[skip..] {
+            let role = if frame.speaker == Speaker::User { "user" } else { "assistant" };
+            messages.push(serde_json::json!({ "role": role, "content": frame.content }));
+        }
+        messages.push(serde_json::json!({ "role": "user", "content": context.analysis.original }));
+        serde_json::json!({ "model": self.model, "messages": messages })
+    }
+
+    async fn complete(&self, context: &ResponseContext<'_>) -> Result<String, reqwest::Error> {
+        let mut request = self.client.post(format!("{}/v1/chat/completions", self.base_url)).json(&self.request_body(context));
+        if let Some(key) = &self.api_key { request = request.bearer_auth(key); }
+        let body: serde_json::Value = request.send().await?.error_for_status()?.json().await?;
+        Ok(body["choices"][0]["message"]["content"].as_str().unwrap_or_default().trim().to_string())
+    }
+
+    // Requests a server-sent event stream and passes every content delta to `delta` as it arrives.
+    async fn complete_stream(&self, context: &ResponseContext<'_>, delta: &mut (dyn FnMut(&str) + Send)) -> Result<(), reqwest::Error> {
+        let mut body = self.request_body(context);
+        body["stream"] = serde_json::Value::Bool(true);
+        let mut request = self.client.post(format!("{}/v1/chat/completions", self.base_url)).json(&body);
+        if let Some(key) = &self.api_key { request = request.bearer_auth(key); }
+        let mut response = request.send().await?.error_for_status()?;
+        let (mut pending, mut started) = (Vec::new(), false);
+        while let Some(bytes) = response.chunk().await? {
+            pending.extend_from_slice(&bytes);
+            while let Some(end) = pending.iter().position(|&byte| byte == b'\n') {
+                let line: Vec<u8> = pending.drain(..=end).collect();
+                let line = String::from_utf8_lossy(&line);
+                let Some(data) = line.trim().strip_prefix("data:").map(str::trim) else { continue };
+                if data == "[DONE]" { return Ok(()); }
+                let Ok(event) = serde_json::from_str::<serde_json::Value>(data) else { continue };
+                let mut piece = event["choices"][0]["delta"]["content"].as_str().unwrap_or_default();
+                if !started { piece = piece.trim_start(); }
+                if piece.is_empty() { continue; }
+                started = true;
+                delta(piece);
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "openai-adapter")]
+impl ResponseGenerator for OpenAiCompatibleGenerator {
+    fn name(&self) -> &'static str { "openai_compatible" }
+
+    fn generate<'a>(&'a self, context: &'a ResponseContext<'a>) -> GenerationFuture<'a> {
+        Box::pin(async move {
+            match self.complete(context).await {
+                Ok(text) if !text.is_empty() => format!("Elias v4.4.1: {}", text),
+                _ => render(context),
+            }
+        })
+    }
+
+    fn generate_stream<'a>(&'a self, context: &'a ResponseContext<'a>, chunk: &'a mut (dyn FnMut(String) + Send)) -> GenerationFuture<'a> {
+        Box::pin(async move {
+            let mut text = String::new();
+            // A connection lost mid-reply keeps what already reached the consumer.
+            let _ = self
+                .complete_stream(context, &mut |piece| {
+                    if text.is_empty() { chunk("Elias v4.4.1: ".to_string()); }
+                    text.push_str(piece);
+                    chunk(piece.to_string());
+                })
+                .await;
+            if !text.is_empty() { return format!("Elias v4.4.1: {}", text.trim_end()); }
+            let response = render(context);
+            for sentence in sentences(&response) { chunk(sentence.to_string()); }
+            response
+        })
+    }
+}
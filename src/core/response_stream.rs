@@ -0,0 +1,27 @@
+use tokio_stream::wrappers::UnboundedReceiverStream;
+use crate::core::coherence::StopReason;
+
+// Progress of one query, in order: the first draft in chunks as the generator produces them,
+// every scored refinement attempt, then exactly one `Done` unless the pipeline fails.
+#[derive(Clone, Debug, PartialEq, serde::Serialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum ResponseEvent {
+    Chunk { text: String },
+    // `best` when the attempt beat every earlier candidate; depth 0 is the unrefined draft.
+    Refinement { depth: usize, strategy: &'static str, score: f64, best: bool },
+    Done { response: String, score: f64, stop_reason: StopReason },
+}
+
+impl ResponseEvent {
+    pub fn name(&self) -> &'static str {
+        match self {
+            ResponseEvent::Chunk { .. } => "chunk",
+            ResponseEvent::Refinement { .. } => "refinement",
+            ResponseEvent::Done { .. } => "done",
+        }
+    }
+
+    pub fn to_json(&self) -> String { serde_json::to_string(self).unwrap() }
+}
+
+pub type ResponseStream = UnboundedReceiverStream<ResponseEvent>;
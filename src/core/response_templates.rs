@@ -0,0 +1,68 @@
+use crate::core::dialogue_frame::{DialogueFrame, Speaker};
+use crate::core::emotional_analytics::Mood;
+use crate::core::text_pipeline::{Intent, QueryAnalysis};
+
+// Everything a response may reference besides the query itself.
+#[derive(Clone)]
+pub struct ResponseContext<'a> {
+    pub analysis: &'a QueryAnalysis,
+    pub mood: Mood,
+    pub valence: f64,
+    pub entropy: f64,
+    pub cosmic_entropy: f64,
+    // The most relevant earlier turn of the session, if any shares keywords with the query.
+    pub related: Option<&'a DialogueFrame>,
+    // The session's dialogue so far (queries and responses), oldest first, excluding the current query.
+    pub history: &'a [DialogueFrame],
+}
+
+impl ResponseContext<'_> {
+    pub fn topic(&self) -> String {
+        match self.analysis.keywords.as_slice() {
+            [] => "that".to_string(),
+            [only] => only.clone(),
+            [init @ .., last] => format!("{} and {}", init.join(", "), last),
+        }
+    }
+
+    pub fn mood_name(&self) -> String { format!("{:?}", self.mood).to_lowercase() }
+}
+
+pub fn render(context: &ResponseContext<'_>) -> String {
+    let topic = context.topic();
+    let opener = match context.analysis.intent {
+        Intent::Greeting => "Elias v4.4.1 greets you.".to_string(),
+        Intent::Farewell => "Elias v4.4.1 bids you farewell.".to_string(),
+        Intent::Question => format!("Elias v4.4.1 reflects on your question about {}.", topic),
+        Intent::Command => format!("Elias v4.4.1 turns its attention to {}.", topic),
+        Intent::Feeling => format!("Elias v4.4.1 hears that you feel {} about {}.", sentiment_word(context.analysis.sentiment), topic),
+        Intent::Statement => format!("Elias v4.4.1 considers what you said about {}.", topic),
+    };
+    let mut response = format!(
+        "{} I feel {} (valence {:+.2}) at entropy {:.2}, cosmic {:.2}.",
+        opener,
+        context.mood_name(),
+        context.valence,
+        context.entropy,
+        context.cosmic_entropy
+    );
+    if context.analysis.sentiment.abs() > 0.2 && context.analysis.intent != Intent::Feeling {
+        response += &format!(" Your words sound {}.", sentiment_word(context.analysis.sentiment));
+    }
+    match context.related {
+        Some(frame) if frame.speaker == Speaker::User => response += &format!(" Earlier you mentioned \"{}\".", frame.content),
+        Some(frame) => response += &format!(" Earlier I said \"{}\".", frame.content),
+        None => {}
+    }
+    response
+}
+
+fn sentiment_word(sentiment: f64) -> &'static str {
+    match sentiment {
+        s if s > 0.5 => "joyful",
+        s if s > 0.2 => "hopeful",
+        s if s < -0.5 => "distressed",
+        s if s < -0.2 => "uneasy",
+        _ => "neutral",
+    }
+}
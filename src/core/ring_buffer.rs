@@ -1,25 +1,156 @@
+use chrono::{DateTime, Utc};
+
+pub trait Timestamped {
+    fn timestamp(&self) -> DateTime<Utc>;
+}
+
 #[derive(Clone)]
 pub struct RingBuffer<T> {
     array: Vec<Option<T>>,
     head: usize,
+    len: usize,
     capacity: usize,
 }
 
-impl<T: Clone> RingBuffer<T> {
+impl<T> RingBuffer<T> {
     pub fn new(capacity: usize) -> Self {
         Self {
-            array: vec![None; capacity],
+            array: (0..capacity).map(|_| None).collect(),
             head: 0,
+            len: 0,
             capacity,
         }
     }
 
-    pub fn append(&mut self, item: T) {
-        self.array[self.head % self.capacity] = Some(item);
-        self.head += 1;
+    pub fn capacity(&self) -> usize { self.capacity }
+
+    pub fn len(&self) -> usize { self.len }
+
+    pub fn is_empty(&self) -> bool { self.len == 0 }
+
+    pub fn is_full(&self) -> bool { self.len == self.capacity }
+
+    // Returns the evicted oldest item once the buffer is full.
+    pub fn append(&mut self, item: T) -> Option<T> {
+        if self.capacity == 0 { return Some(item); }
+        let evicted = self.array[self.head].replace(item);
+        self.head = (self.head + 1) % self.capacity;
+        if self.len < self.capacity { self.len += 1; }
+        evicted
     }
 
     pub fn get_latest(&self) -> Option<&T> {
-        self.array[(self.head.wrapping_sub(1) % self.capacity) as usize].as_ref()
+        self.len.checked_sub(1).and_then(|i| self.get(i))
+    }
+
+    pub fn get_oldest(&self) -> Option<&T> { self.get(0) }
+
+    // Index 0 is the oldest item, `len() - 1` the newest.
+    pub fn get(&self, index: usize) -> Option<&T> {
+        if index >= self.len { return None; }
+        self.array[self.physical_index(index)].as_ref()
+    }
+
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter { buffer: self, front: 0, back: self.len }
+    }
+
+    pub fn iter_rev(&self) -> std::iter::Rev<Iter<'_, T>> { self.iter().rev() }
+
+    // The newest `k` items, oldest first.
+    pub fn last_n(&self, k: usize) -> Iter<'_, T> {
+        Iter { buffer: self, front: self.len.saturating_sub(k), back: self.len }
+    }
+
+    // Removes every item, yielding them oldest first.
+    pub fn drain(&mut self) -> std::vec::IntoIter<T> {
+        let mut items = Vec::with_capacity(self.len);
+        for logical in 0..self.len {
+            let index = self.physical_index(logical);
+            items.extend(self.array[index].take());
+        }
+        self.head = 0;
+        self.len = 0;
+        items.into_iter()
+    }
+
+    pub fn clear(&mut self) { self.drain(); }
+
+    // Keeps the newest `min(len, new_capacity)` items in order.
+    pub fn resize(&mut self, new_capacity: usize) {
+        let items: Vec<T> = self.drain().collect();
+        let skip = items.len().saturating_sub(new_capacity);
+        *self = Self::new(new_capacity);
+        self.extend(items.into_iter().skip(skip));
+    }
+
+    fn physical_index(&self, logical: usize) -> usize {
+        (self.head + self.capacity - self.len + logical) % self.capacity
+    }
+}
+
+impl<T: Timestamped> RingBuffer<T> {
+    // Items with `from <= timestamp < to`, oldest first; assumes appends arrive in time order.
+    pub fn window(&self, from: DateTime<Utc>, to: DateTime<Utc>) -> impl Iterator<Item = &T> {
+        let start = self.partition_point(|item| item.timestamp() < from);
+        let end = self.partition_point(|item| item.timestamp() < to).max(start);
+        Iter { buffer: self, front: start, back: end }
+    }
+
+    pub fn since(&self, from: DateTime<Utc>) -> impl Iterator<Item = &T> {
+        let start = self.partition_point(|item| item.timestamp() < from);
+        Iter { buffer: self, front: start, back: self.len }
+    }
+
+    fn partition_point(&self, pred: impl Fn(&T) -> bool) -> usize {
+        let (mut lo, mut hi) = (0, self.len);
+        while lo < hi {
+            let mid = lo + (hi - lo) / 2;
+            if self.get(mid).is_some_and(&pred) { lo = mid + 1; } else { hi = mid; }
+        }
+        lo
+    }
+}
+
+impl<T> Extend<T> for RingBuffer<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for item in iter { self.append(item); }
+    }
+}
+
+impl<'a, T> IntoIterator for &'a RingBuffer<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+    fn into_iter(self) -> Self::IntoIter { self.iter() }
+}
+
+pub struct Iter<'a, T> {
+    buffer: &'a RingBuffer<T>,
+    front: usize,
+    back: usize,
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        if self.front >= self.back { return None; }
+        self.front += 1;
+        self.buffer.get(self.front - 1)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.back.saturating_sub(self.front);
+        (remaining, Some(remaining))
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for Iter<'a, T> {
+    fn next_back(&mut self) -> Option<&'a T> {
+        if self.front >= self.back { return None; }
+        self.back -= 1;
+        self.buffer.get(self.back)
     }
 }
+
+impl<'a, T> ExactSizeIterator for Iter<'a, T> {}
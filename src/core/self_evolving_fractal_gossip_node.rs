@@ -1,14 +1,28 @@
 use std::sync::atomic::{AtomicUsize, Ordering};
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 use tokio::sync::Semaphore;
 use tokio::time::{sleep, Duration};
+use tokio::net::TcpListener;
+use crate::core::chaos_time_series::ChaosTimeSeries;
+use crate::core::dialogue_sessions::DEFAULT_SESSION;
+use crate::core::emotional_contagion::ContagionConfig;
 use crate::core::elias_nlp_interface::EliasNLPInterface;
-use crate::core::emotional_state_model::EmotionalStateModel;
-use crate::core::self_model::SelfModel;
+use crate::core::emotional_state_model::{Appraisal, EmotionalStateModel};
+use crate::core::query_cache::{CacheConfig, QueryCache, StateEpoch};
+use crate::core::response_stream::ResponseStream;
+use crate::core::self_model::{IntrospectionReport, SelfModel};
+use crate::dynamics::chaos_source::ChaosSource;
+use crate::dynamics::chaotic_systems::{ChaoticSystem, Lorenz};
 use crate::network::cosmic_gossip_protocol::{CosmicGossipProtocol, State};
+use crate::network::distributed_query::{DistributedAnswer, FanOutConfig, QueryFanOut};
+use crate::network::local_api::{LocalApi, QueryRequest};
+use crate::network::network_metrics::NetworkMetrics;
 use crate::network::peer_discovery::PeerDiscovery;
+use crate::quantum::entropy_estimators::EntropyConfig;
+use crate::quantum::fractal_generators::{FractalGenerator, FractalKind, FractalParams};
 use crate::quantum::quantum_fractal_tensor_engine::QuantumFractalTensorEngine;
 use crate::rendering::cross_modal_cosmic_engine::CrossModalCosmicEngine;
+use crate::storage::dialogue_export::export_dialogue;
 use crate::storage::redis_interface::RedisInterface;
 use crate::storage::state_manager::StateManager;
 
@@ -16,9 +30,20 @@ pub struct SelfEvolvingFractalGossipNode {
     peer_id: String,
     entropy: AtomicUsize,
     active_nodes: AtomicUsize,
-    chaos_history: Vec<Vec<f64>>,
+    chaos_history: ChaosTimeSeries,
+    // Shared with the sync loop so `set_chaos_system` reaches the running node.
+    chaos_source: Arc<Mutex<ChaosSource>>,
     tensor_engine: QuantumFractalTensorEngine,
+    fractal_kind: FractalKind,
+    // Shared with the sync loop so `set_entropy_config` reaches the running node.
+    entropy_config: Arc<Mutex<EntropyConfig>>,
     emotional_state_model: EmotionalStateModel,
+    // Shared with the sync loop so `set_contagion` reaches the running node.
+    contagion: Arc<Mutex<ContagionConfig>>,
+    fan_out: Option<FanOutConfig>,
+    query_cache: QueryCache,
+    // Shared with the spawned loops, which observe into it every tick.
+    self_model: Arc<Mutex<SelfModel>>,
     cross_modal_engine: CrossModalCosmicEngine,
     nli: EliasNLPInterface,
     state_manager: StateManager,
@@ -32,31 +57,119 @@ impl SelfEvolvingFractalGossipNode {
             peer_id: peer_id.clone(),
             entropy: AtomicUsize::new(0),
             active_nodes: AtomicUsize::new(5000),
-            chaos_history: Vec::with_capacity(10000),
+            chaos_history: ChaosTimeSeries::new(10000, 7 * 24 * 60, 90 * 24).with_persistence(StateManager::new(peer_id.clone())),
+            chaos_source: Arc::new(Mutex::new(ChaosSource::new(ChaoticSystem::Lorenz(Lorenz::classic())))),
             tensor_engine: QuantumFractalTensorEngine::new(),
+            fractal_kind: FractalKind::Julia,
+            entropy_config: Arc::new(Mutex::new(EntropyConfig::default())),
             emotional_state_model: EmotionalStateModel::new(),
+            contagion: Arc::new(Mutex::new(ContagionConfig::new(0.2))),
+            fan_out: None,
+            query_cache: QueryCache::new(CacheConfig::new()),
+            self_model: Arc::new(Mutex::new(SelfModel::new())),
             cross_modal_engine: CrossModalCosmicEngine::new(),
-            nli: EliasNLPInterface::new(),
+            nli: EliasNLPInterface::new().with_dialogue_log(StateManager::new(peer_id.clone())),
             state_manager: StateManager::new(peer_id.clone()),
             redis: RedisInterface::new("localhost", 6379 + peer_id.split('_').last().unwrap().parse::<u16>()?),
             peers: Vec::new(),
         };
+        let (nli, self_model) = (node.nli.clone(), node.self_model.clone());
+        QueryFanOut::register(&peer_id, Arc::new(move |request: QueryRequest| {
+            let (nli, self_model) = (nli.clone(), self_model.clone());
+            Box::pin(async move { nli.process_session_query(&request.session_id, request.query, request.depth, self_model).await })
+        }));
+        CosmicGossipProtocol::listen(&peer_id);
         PeerDiscovery::register_node(peer_id);
         tokio::spawn(node.clone().cosmic_sync_loop());
         tokio::spawn(node.clone().render_cross_modal_loop());
         Ok(node)
     }
 
+    pub fn set_entropy_config(&self, config: EntropyConfig) {
+        *self.entropy_config.lock().unwrap() = config;
+    }
+
+    pub fn set_chaos_system(&self, system: ChaoticSystem) {
+        *self.chaos_source.lock().unwrap() = ChaosSource::new(system);
+    }
+
+    pub fn set_contagion(&self, contagion: ContagionConfig) {
+        *self.contagion.lock().unwrap() = contagion;
+    }
+
+    // When set, `process_query` also asks the closest peers and returns the combined answer.
+    pub fn set_fan_out(&mut self, fan_out: Option<FanOutConfig>) {
+        self.fan_out = fan_out;
+    }
+
+    pub fn query_cache(&self) -> &QueryCache { &self.query_cache }
+
+    pub fn nli(&self) -> &EliasNLPInterface { &self.nli }
+
+    pub fn introspect(&self) -> IntrospectionReport { self.self_model.lock().unwrap().introspect() }
+
+    // Reconfigures the cache shared with the sync loop, dropping every cached response.
+    pub fn set_query_cache(&self, config: CacheConfig) {
+        self.query_cache.reconfigure(config);
+    }
+
+    // Taken from the shared self model: the sync loop evolves its own clone of the node, so this
+    // handle's entropy and emotions never move.
+    fn state_epoch(&self) -> StateEpoch {
+        let self_model = self.self_model.lock().unwrap();
+        self.query_cache.epoch(self_model.entropy(), self_model.valence(), self_model.arousal())
+    }
+
+    // Repeated queries in the same state epoch are answered from the cache, skipping the
+    // network sync and the dialogue pipeline; the turn is still logged.
     pub async fn process_query(&self, query: String) -> String {
+        let epoch = self.state_epoch();
+        if let Some(response) = self.query_cache.get(&query, &epoch) {
+            let latest = self.self_model.lock().unwrap().latest();
+            self.nli.record_turn(DEFAULT_SESSION, &query, &response, 0, &latest);
+            return response;
+        }
         let semaphore = Arc::new(Semaphore::new(std::cmp::max(500, self.active_nodes.load(Ordering::Relaxed) / 1000)));
         let permit = semaphore.acquire().await.unwrap();
         self.synchronize_with_network(PeerDiscovery::get_peers(self).await).await;
-        let response = self.nli.process_query(query, 0, SelfModel::new()).await;
+        let response = match &self.fan_out {
+            Some(config) => self.answer_with_peers(query.clone(), config).await.response,
+            None => self.nli.process_query(query.clone(), 0, self.self_model.clone()).await,
+        };
         drop(permit);
+        self.query_cache.insert(&query, &epoch, &response);
         response
     }
 
-    async fn cosmic_sync_loop(self) {
+    // Like `process_query` with fan-out, returning which peers contributed.
+    pub async fn process_query_distributed(&self, query: String, config: &FanOutConfig) -> DistributedAnswer {
+        self.synchronize_with_network(PeerDiscovery::get_peers(self).await).await;
+        self.answer_with_peers(query, config).await
+    }
+
+    async fn answer_with_peers(&self, query: String, config: &FanOutConfig) -> DistributedAnswer {
+        let peers = PeerDiscovery::closest_peers(&self.peer_id, &QueryFanOut::reachable(&self.peers), config.k);
+        let local = self.nli.process_query(query.clone(), 0, self.self_model.clone());
+        QueryFanOut::fan_out(&self.peer_id, &query, local, &peers, config).await
+    }
+
+    pub async fn process_query_stream(&self, query: String) -> ResponseStream {
+        self.synchronize_with_network(PeerDiscovery::get_peers(self).await).await;
+        self.nli.process_query_stream(DEFAULT_SESSION, query, 0, self.self_model.clone())
+    }
+
+    // Relays streamed responses to local clients and serves the dialogue log export; runs until
+    // the task is dropped.
+    pub async fn serve_local_api(&self, listener: TcpListener) {
+        let (nli, self_model) = (self.nli.clone(), self.self_model.clone());
+        let mut api = LocalApi::new(move |request| nli.process_query_stream(&request.session_id, request.query, request.depth, self_model.clone()));
+        if let Some(manager) = self.nli.sessions().persistence().cloned() {
+            api = api.with_exporter(move |request| export_dialogue(&manager, request.session_id.as_deref(), request.search.as_deref(), request.limit, request.format));
+        }
+        api.serve(listener).await
+    }
+
+    async fn cosmic_sync_loop(mut self) {
         loop {
             let peers = PeerDiscovery::get_peers(&self).await;
             let global_peers: Vec<_> = peers.iter().filter(|p| p.contains("global")).cloned().collect();
@@ -64,8 +177,37 @@ impl SelfEvolvingFractalGossipNode {
             let sync_size = std::cmp::min(global_peers.len(), bandwidth_limit / 1000); // 1KB/state
             let cosmic_entropy = CosmicEntropy::calculate(&self);
             self.entropy.store(cosmic_entropy as usize, Ordering::Relaxed);
-            self.chaos_history.push(vec![cosmic_entropy]);
-            if self.chaos_history.len() > 10000 { self.chaos_history.remove(0); }
+            let chaos = self.chaos_source.lock().unwrap().step(self.active_nodes.load(Ordering::Relaxed), self.tensor_engine.last_statistics());
+            self.chaos_history.record(chaos);
+            let history = self.chaos_history.totals();
+            let params = FractalParams::from_node_state(self.fractal_kind, cosmic_entropy, self.emotional_state_model.get_current_valence(), &history);
+            // Rendering the fractal is CPU-bound, so it runs off the async executor.
+            let (generator, shape) = (FractalGenerator::new(params), self.tensor_engine.tensor_field.shape());
+            let image = tokio::task::spawn_blocking(move || generator.render_for(shape)).await.unwrap();
+            self.tensor_engine.blend_fractal_image(&image, 0.1);
+            let appraisal = Appraisal {
+                field: self.tensor_engine.last_statistics().cloned(),
+                cosmic_entropy: Some(cosmic_entropy),
+                network_health: Some(NetworkMetrics::new().health(&self)),
+                ..Default::default()
+            };
+            self.emotional_state_model.appraise(&appraisal);
+            let summary = self.emotional_state_model.summary(&self.peer_id);
+            CosmicGossipProtocol::new().gossip_emotion(&summary, &self.peers, rand::random::<f64>() * 0.1).await;
+            let neighbors = CosmicGossipProtocol::take_emotions(&self.peer_id);
+            let contagion = self.contagion.lock().unwrap().clone();
+            self.emotional_state_model.blend_with_neighbors(&neighbors, &contagion);
+            let observation = SelfModel::observation(&self);
+            let alerts = {
+                let mut self_model = self.self_model.lock().unwrap();
+                let alerts = self_model.observe(observation);
+                self_model.observe_peer_views(&neighbors);
+                alerts
+            };
+            for alert in alerts {
+                println!("Warning: {} forecast error {:.3} exceeded {:.3}", alert.variable.name(), alert.error, alert.threshold);
+            }
+            self.query_cache.observe_epoch(self.state_epoch());
             self.synchronize_with_network(peers.into_iter().take(sync_size).collect()).await;
             sleep(Duration::from_millis(500)).await;
         }
@@ -83,6 +225,7 @@ impl SelfEvolvingFractalGossipNode {
         let state = State { cid: uuid::Uuid::new_v4().to_string(), encrypted: format!("data_{}", chrono::Utc::now()) };
         let churn = rand::random::<f64>();
         if CosmicGossipProtocol::new().propagate_state(&state, &peers, churn).await {
+            PeerDiscovery::record_links(&self.peer_id, &peers);
             self.state_manager.save_state(&state);
             self.redis.cache_state(&state).await;
             self.peers = peers;
@@ -93,5 +236,5 @@ impl SelfEvolvingFractalGossipNode {
 
 impl Clone for SelfEvolvingFractalGossipNode {
     fn clone(&self) -> Self {
-        Self { peer_id: self.peer_id.clone(), entropy: AtomicUsize::new(self.entropy.load(Ordering::Relaxed)), active_nodes: AtomicUsize::new(self.active_nodes.load(Ordering::Relaxed)), chaos_history: self.chaos_history.clone(), tensor_engine: self.tensor_engine.clone(), emotional_state_model: self.emotional_state_model.clone(), cross_modal_engine: self.cross_modal_engine.clone(), nli: self.nli.clone(), state_manager: self.state_manager.clone(), redis: self.redis.clone(), peers: self.peers.clone() }
+        Self { peer_id: self.peer_id.clone(), entropy: AtomicUsize::new(self.entropy.load(Ordering::Relaxed)), active_nodes: AtomicUsize::new(self.active_nodes.load(Ordering::Relaxed)), chaos_history: self.chaos_history.clone(), chaos_source: self.chaos_source.clone(), tensor_engine: self.tensor_engine.clone(), fractal_kind: self.fractal_kind, entropy_config: self.entropy_config.clone(), emotional_state_model: self.emotional_state_model.clone(), contagion: self.contagion.clone(), fan_out: self.fan_out.clone(), query_cache: self.query_cache.clone(), self_model: self.self_model.clone(), cross_modal_engine: self.cross_modal_engine.clone(), nli: self.nli.clone(), state_manager: self.state_manager.clone(), redis: self.redis.clone(), peers: self.peers.clone() }
 }
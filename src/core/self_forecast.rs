@@ -0,0 +1,222 @@
+use chrono::{DateTime, Utc};
+use crate::core::ring_buffer::{RingBuffer, Timestamped};
+use crate::core::self_model::SelfObservation;
+
+// Initial variance of each weight, and the per-weight cap on P's trace.
+const INITIAL_COVARIANCE: f64 = 1000.0;
+
+// Online AR(`order`) model with intercept, fitted by recursive least squares. `forgetting` < 1
+// discounts old samples so the fit tracks a drifting node. A constant input excites no direction,
+// so forgetting alone would grow P by 1/forgetting per tick until it overflows; P is rescaled
+// whenever its trace passes `INITIAL_COVARIANCE` per weight.
+#[derive(Clone, Debug)]
+pub struct ArForecaster {
+    pub order: usize,
+    pub forgetting: f64,
+    weights: Vec<f64>,
+    covariance: Vec<Vec<f64>>,
+    // The most recent `order` values, newest first.
+    lags: Vec<f64>,
+}
+
+impl ArForecaster {
+    pub fn new(order: usize, forgetting: f64) -> Self {
+        let n = order + 1;
+        Self {
+            order,
+            forgetting,
+            weights: vec![0.0; n],
+            covariance: (0..n).map(|i| (0..n).map(|j| if i == j { INITIAL_COVARIANCE } else { 0.0 }).collect()).collect(),
+            lags: Vec::with_capacity(order),
+        }
+    }
+
+    pub fn weights(&self) -> &[f64] { &self.weights }
+
+    fn regressors(&self) -> Vec<f64> { std::iter::once(1.0).chain(self.lags.iter().copied()).collect() }
+
+    // None until `order` values have been seen.
+    pub fn forecast(&self) -> Option<f64> {
+        (self.lags.len() == self.order).then(|| self.regressors().iter().zip(&self.weights).map(|(x, w)| x * w).sum())
+    }
+
+    // Learns from `value` and returns the signed error (actual - forecast) of the forecast it replaces.
+    pub fn update(&mut self, value: f64) -> Option<f64> {
+        let error = self.forecast().map(|forecast| value - forecast);
+        if let Some(error) = error {
+            let x = self.regressors();
+            let px: Vec<f64> = self.covariance.iter().map(|row| row.iter().zip(&x).map(|(p, x)| p * x).sum()).collect();
+            let denominator = self.forgetting + x.iter().zip(&px).map(|(x, px)| x * px).sum::<f64>();
+            let gain: Vec<f64> = px.iter().map(|px| px / denominator).collect();
+            for (weight, gain) in self.weights.iter_mut().zip(&gain) { *weight += gain * error; }
+            // P is symmetric, so xᵀP = (Px)ᵀ.
+            for (i, row) in self.covariance.iter_mut().enumerate() {
+                for (j, p) in row.iter_mut().enumerate() { *p = (*p - gain[i] * px[j]) / self.forgetting; }
+            }
+            let trace: f64 = (0..self.covariance.len()).map(|i| self.covariance[i][i]).sum();
+            let bound = INITIAL_COVARIANCE * self.covariance.len() as f64;
+            if trace > bound {
+                for p in self.covariance.iter_mut().flatten() { *p *= bound / trace; }
+            }
+        }
+        if self.order > 0 {
+            if self.lags.len() == self.order { self.lags.pop(); }
+            self.lags.insert(0, value);
+        }
+        error
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, serde::Serialize, serde::Deserialize)]
+pub enum SelfVariable {
+    Entropy,
+    Valence,
+    CosmicEntropy,
+}
+
+impl SelfVariable {
+    pub const ALL: [SelfVariable; 3] = [SelfVariable::Entropy, SelfVariable::Valence, SelfVariable::CosmicEntropy];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            SelfVariable::Entropy => "entropy",
+            SelfVariable::Valence => "valence",
+            SelfVariable::CosmicEntropy => "cosmic_entropy",
+        }
+    }
+
+    pub fn of(&self, observation: &SelfObservation) -> f64 {
+        match self {
+            SelfVariable::Entropy => observation.entropy,
+            SelfVariable::Valence => observation.pleasure,
+            SelfVariable::CosmicEntropy => observation.cosmic_entropy,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Forecast {
+    pub entropy: Option<f64>,
+    pub valence: Option<f64>,
+    pub cosmic_entropy: Option<f64>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ForecastRecord {
+    pub timestamp: DateTime<Utc>,
+    pub variable: SelfVariable,
+    pub forecast: f64,
+    pub actual: f64,
+}
+
+impl ForecastRecord {
+    pub fn error(&self) -> f64 { self.actual - self.forecast }
+}
+
+impl Timestamped for ForecastRecord {
+    fn timestamp(&self) -> DateTime<Utc> { self.timestamp }
+}
+
+// Raised when an absolute forecast error exceeds `threshold`: the running mean plus
+// `spike_factor` standard deviations of that variable's earlier absolute errors, but at least
+// `min_error` so a perfectly predicted variable doesn't alert on rounding noise.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ForecastAlert {
+    pub timestamp: DateTime<Utc>,
+    pub variable: SelfVariable,
+    pub error: f64,
+    pub threshold: f64,
+}
+
+impl Timestamped for ForecastAlert {
+    fn timestamp(&self) -> DateTime<Utc> { self.timestamp }
+}
+
+// Exponentially weighted mean and variance of absolute errors.
+#[derive(Clone, Copy, Debug, Default)]
+struct ErrorStats {
+    mean: f64,
+    variance: f64,
+    count: usize,
+}
+
+impl ErrorStats {
+    fn push(&mut self, value: f64, smoothing: f64) {
+        self.count += 1;
+        if self.count == 1 {
+            self.mean = value;
+            return;
+        }
+        let delta = value - self.mean;
+        self.mean += smoothing * delta;
+        self.variance = (1.0 - smoothing) * (self.variance + smoothing * delta * delta);
+    }
+}
+
+#[derive(Clone)]
+pub struct SelfForecaster {
+    pub spike_factor: f64,
+    pub min_error: f64,
+    // Errors scored before alerts may fire, per variable.
+    pub warmup: usize,
+    models: Vec<(SelfVariable, ArForecaster, ErrorStats)>,
+    error_log: RingBuffer<ForecastRecord>,
+    alerts: RingBuffer<ForecastAlert>,
+}
+
+impl SelfForecaster {
+    pub fn new(order: usize) -> Self {
+        Self {
+            spike_factor: 4.0,
+            min_error: 1e-3,
+            warmup: 20,
+            models: SelfVariable::ALL.iter().map(|variable| (*variable, ArForecaster::new(order, 0.98), ErrorStats::default())).collect(),
+            error_log: RingBuffer::new(3 * 1000),
+            alerts: RingBuffer::new(100),
+        }
+    }
+
+    pub fn forecast_of(&self, variable: SelfVariable) -> Option<f64> {
+        self.models.iter().find(|(candidate, _, _)| *candidate == variable).and_then(|(_, model, _)| model.forecast())
+    }
+
+    pub fn forecast(&self) -> Forecast {
+        Forecast {
+            entropy: self.forecast_of(SelfVariable::Entropy),
+            valence: self.forecast_of(SelfVariable::Valence),
+            cosmic_entropy: self.forecast_of(SelfVariable::CosmicEntropy),
+        }
+    }
+
+    // Scores the pending forecasts against `observation`, learns from it, and returns any alerts.
+    pub fn observe_at(&mut self, observation: &SelfObservation, at: DateTime<Utc>) -> Vec<ForecastAlert> {
+        let mut raised = Vec::new();
+        for (variable, model, stats) in self.models.iter_mut() {
+            let actual = variable.of(observation);
+            let pending = model.forecast();
+            model.update(actual);
+            let Some(forecast) = pending else { continue };
+            let error = (actual - forecast).abs();
+            self.error_log.append(ForecastRecord { timestamp: at, variable: *variable, forecast, actual });
+            let threshold = (stats.mean + self.spike_factor * stats.variance.sqrt()).max(self.min_error);
+            if stats.count >= self.warmup && error > threshold {
+                let alert = ForecastAlert { timestamp: at, variable: *variable, error, threshold };
+                self.alerts.append(alert);
+                raised.push(alert);
+            }
+            stats.push(error, 0.1);
+        }
+        raised
+    }
+
+    // Oldest first.
+    pub fn error_log(&self) -> &RingBuffer<ForecastRecord> { &self.error_log }
+
+    pub fn alerts(&self) -> &RingBuffer<ForecastAlert> { &self.alerts }
+
+    // Mean absolute error over the newest `window` forecasts of `variable`.
+    pub fn mean_error(&self, variable: SelfVariable, window: usize) -> Option<f64> {
+        let errors: Vec<f64> = self.error_log.iter_rev().filter(|record| record.variable == variable).take(window).map(|record| record.error().abs()).collect();
+        (!errors.is_empty()).then(|| errors.iter().sum::<f64>() / errors.len() as f64)
+    }
+}
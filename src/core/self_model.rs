@@ -1,12 +1,103 @@
 use std::collections::HashMap;
+use std::sync::atomic::Ordering;
+use chrono::{DateTime, Utc};
+use crate::core::emotional_contagion::EmotionalSummary;
+use crate::core::emotional_state_model::Dimension;
+use crate::core::ring_buffer::RingBuffer;
+use crate::core::self_forecast::{Forecast, ForecastAlert, SelfForecaster, SelfVariable};
 use crate::core::self_evolving_fractal_gossip_node::SelfEvolvingFractalGossipNode;
 
+const MAX_LEVELS: usize = 4;
+const SMOOTHING: f64 = 0.5;
+
+// One tick's worth of what the node can observe about itself.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct SelfObservation {
+    pub entropy: f64,
+    pub pleasure: f64,
+    pub arousal: f64,
+    pub dominance: f64,
+    pub cosmic_entropy: f64,
+}
+
+// Level k forecasts its target by exponential smoothing: level 0 targets entropy, level k + 1
+// targets the absolute error of level k. Skill compares its error with that of always guessing
+// the target's running mean.
+#[derive(Clone, Debug)]
+struct PredictionLevel {
+    prediction: Option<f64>,
+    mean: f64,
+    count: usize,
+    model_error: f64,
+    baseline_error: f64,
+    scored: usize,
+}
+
+impl PredictionLevel {
+    fn new() -> Self { Self { prediction: None, mean: 0.0, count: 0, model_error: 0.0, baseline_error: 0.0, scored: 0 } }
+
+    // Returns the absolute error of the previous prediction, if there was one.
+    fn observe(&mut self, target: f64) -> Option<f64> {
+        let error = self.prediction.map(|prediction| (target - prediction).abs());
+        if let Some(error) = error {
+            let baseline = (target - self.mean).abs();
+            let weight = if self.scored == 0 { 1.0 } else { SMOOTHING };
+            self.model_error += weight * (error - self.model_error);
+            self.baseline_error += weight * (baseline - self.baseline_error);
+            self.scored += 1;
+        }
+        self.count += 1;
+        self.mean += (target - self.mean) / self.count as f64;
+        self.prediction = Some(self.prediction.map_or(target, |prediction| prediction + SMOOTHING * (target - prediction)));
+        error
+    }
+
+    // In [0, 1]; a level that has never been scored has no skill.
+    fn skill(&self) -> f64 {
+        if self.scored == 0 { return 0.0; }
+        if self.baseline_error <= 1e-12 { return if self.model_error <= 1e-12 { 1.0 } else { 0.0 }; }
+        (1.0 - self.model_error / self.baseline_error).clamp(0.0, 1.0)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct LevelReport {
+    pub level: usize,
+    pub prediction: Option<f64>,
+    pub mean_error: f64,
+    pub skill: f64,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct IntrospectionReport {
+    pub timestamp: DateTime<Utc>,
+    pub observation: SelfObservation,
+    pub observations: usize,
+    // Level 0's forecast of the next entropy and the error of its last forecast.
+    pub entropy_prediction: Option<f64>,
+    pub entropy_prediction_error: Option<f64>,
+    // 1 for a constant valence, falling to 0 as its recent standard deviation reaches 1.
+    pub emotional_stability: f64,
+    // How closely neighbours' gossiped moods match this node's, in [0, 1]; None before any arrive.
+    pub peer_agreement: Option<f64>,
+    pub levels: Vec<LevelReport>,
+    pub depth: f64,
+    // The AR forecaster's prediction of the next tick and its recent mean absolute errors.
+    pub forecast: Forecast,
+    pub forecast_errors: Forecast,
+}
+
 #[derive(Clone)]
 pub struct SelfModel {
     self_state: HashMap<String, f64>,
     quantum_state: HashMap<String, f64>,
-    cached_depth: Option<f64>,
-    last_update: chrono::DateTime<chrono::Utc>,
+    levels: Vec<PredictionLevel>,
+    last_entropy_error: Option<f64>,
+    valence_history: RingBuffer<f64>,
+    peer_agreement: Option<f64>,
+    observations: usize,
+    last_update: DateTime<Utc>,
+    forecaster: SelfForecaster,
 }
 
 impl SelfModel {
@@ -14,26 +105,129 @@ impl SelfModel {
         Self {
             self_state: HashMap::from([("entropy".to_string(), 0.0), ("valence".to_string(), 0.0)]),
             quantum_state: HashMap::from([("cosmic_entropy".to_string(), 0.0)]),
-            cached_depth: None,
-            last_update: chrono::DateTime::<chrono::Utc>::MIN_UTC,
+            levels: (0..MAX_LEVELS).map(|_| PredictionLevel::new()).collect(),
+            last_entropy_error: None,
+            valence_history: RingBuffer::new(32),
+            peer_agreement: None,
+            observations: 0,
+            last_update: DateTime::<Utc>::MIN_UTC,
+            forecaster: SelfForecaster::new(2),
+        }
+    }
+
+    pub fn observation(node: &SelfEvolvingFractalGossipNode) -> SelfObservation {
+        let emotions = &node.emotional_state_model;
+        SelfObservation {
+            entropy: node.entropy.load(Ordering::Relaxed) as f64,
+            pleasure: emotions.get(&Dimension::Pleasure),
+            arousal: emotions.get(&Dimension::Arousal),
+            dominance: emotions.get(&Dimension::Dominance),
+            cosmic_entropy: crate::quantum::cosmic_entropy::CosmicEntropy::calculate(node),
         }
     }
 
-    pub fn update_self(&mut self, node: &SelfEvolvingFractalGossipNode) {
-        self.self_state.insert("entropy".to_string(), node.entropy.load(Ordering::Relaxed) as f64);
-        self.self_state.insert("valence".to_string(), node.emotional_state_model.get_current_valence());
-        self.quantum_state.insert("cosmic_entropy".to_string(), crate::quantum::cosmic_entropy::CosmicEntropy::calculate(node));
+    pub fn update_self(&mut self, node: &SelfEvolvingFractalGossipNode) { self.observe(Self::observation(node)); }
+
+    pub fn observe(&mut self, observation: SelfObservation) -> Vec<ForecastAlert> { self.observe_at(observation, Utc::now()) }
+
+    // Returns an alert for every variable whose forecast error just spiked.
+    pub fn observe_at(&mut self, observation: SelfObservation, at: DateTime<Utc>) -> Vec<ForecastAlert> {
+        self.self_state.insert("entropy".to_string(), observation.entropy);
+        self.self_state.insert("valence".to_string(), observation.pleasure);
+        self.self_state.insert("arousal".to_string(), observation.arousal);
+        self.self_state.insert("dominance".to_string(), observation.dominance);
+        self.quantum_state.insert("cosmic_entropy".to_string(), observation.cosmic_entropy);
+        self.valence_history.append(observation.pleasure);
+        // Each level's error feeds the next; a level without a prior forecast ends the cascade.
+        let mut target = observation.entropy;
+        for (level, predictor) in self.levels.iter_mut().enumerate() {
+            let Some(error) = predictor.observe(target) else { break };
+            if level == 0 { self.last_entropy_error = Some(error); }
+            target = error;
+        }
+        self.observations += 1;
+        self.last_update = at;
+        self.forecaster.observe_at(&observation, at)
+    }
+
+    pub fn forecaster(&self) -> &SelfForecaster { &self.forecaster }
+
+    pub fn set_forecaster(&mut self, forecaster: SelfForecaster) { self.forecaster = forecaster; }
+
+    // Agreement is 1 minus the mean PAD distance to each neighbour, scaled into [0, 1].
+    pub fn observe_peer_views(&mut self, neighbors: &[EmotionalSummary]) {
+        if neighbors.is_empty() { return; }
+        let own = [self.valence(), self.state("arousal"), self.state("dominance")];
+        let distance: f64 = neighbors
+            .iter()
+            .map(|neighbor| [neighbor.pleasure, neighbor.arousal, neighbor.dominance].iter().zip(&own).map(|(theirs, ours)| (theirs - ours).abs()).sum::<f64>() / 6.0)
+            .sum::<f64>()
+            / neighbors.len() as f64;
+        self.peer_agreement = Some((1.0 - distance).clamp(0.0, 1.0));
+    }
+
+    fn state(&self, key: &str) -> f64 { self.self_state.get(key).copied().unwrap_or(0.0) }
+
+    pub fn entropy(&self) -> f64 { self.state("entropy") }
+
+    pub fn valence(&self) -> f64 { self.state("valence") }
+
+    pub fn arousal(&self) -> f64 { self.state("arousal") }
+
+    // The most recent observation; all zeros before the first.
+    pub fn latest(&self) -> SelfObservation {
+        SelfObservation {
+            entropy: self.entropy(),
+            pleasure: self.valence(),
+            arousal: self.arousal(),
+            dominance: self.state("dominance"),
+            cosmic_entropy: self.cosmic_entropy(),
+        }
+    }
+
+    pub fn cosmic_entropy(&self) -> f64 { self.quantum_state.get("cosmic_entropy").copied().unwrap_or(0.0) }
+
+    // Having observed itself at all counts 1; each prediction level then adds its skill,
+    // discounted by the skills of the levels beneath it: depth = 1 + s0 + s0*s1 + ...
+    pub fn get_recursive_depth(&self) -> f64 {
+        if self.observations == 0 { return 0.0; }
+        let mut compounded = 1.0;
+        1.0 + self.levels.iter().map(|level| {
+            compounded *= level.skill();
+            compounded
+        }).sum::<f64>()
+    }
+
+    pub fn emotional_stability(&self) -> f64 {
+        let n = self.valence_history.len();
+        if n < 2 { return 1.0; }
+        let mean = self.valence_history.iter().sum::<f64>() / n as f64;
+        let variance = self.valence_history.iter().map(|value| (value - mean).powi(2)).sum::<f64>() / n as f64;
+        (1.0 - variance.sqrt()).clamp(0.0, 1.0)
     }
 
-    pub fn get_recursive_depth(&mut self) -> f64 {
-        if let Some(depth) = self.cached_depth {
-            if chrono::Utc::now().signed_duration_since(self.last_update).num_seconds() < 5 {
-                return depth;
-            }
+    pub fn introspect(&self) -> IntrospectionReport {
+        IntrospectionReport {
+            timestamp: self.last_update,
+            observation: self.latest(),
+            observations: self.observations,
+            entropy_prediction: self.levels[0].prediction,
+            entropy_prediction_error: self.last_entropy_error,
+            emotional_stability: self.emotional_stability(),
+            peer_agreement: self.peer_agreement,
+            levels: self
+                .levels
+                .iter()
+                .enumerate()
+                .map(|(level, predictor)| LevelReport { level, prediction: predictor.prediction, mean_error: predictor.model_error, skill: predictor.skill() })
+                .collect(),
+            depth: self.get_recursive_depth(),
+            forecast: self.forecaster.forecast(),
+            forecast_errors: Forecast {
+                entropy: self.forecaster.mean_error(SelfVariable::Entropy, 20),
+                valence: self.forecaster.mean_error(SelfVariable::Valence, 20),
+                cosmic_entropy: self.forecaster.mean_error(SelfVariable::CosmicEntropy, 20),
+            },
         }
-        let depth = (1.0 + self.self_state.len() as f64 + self.quantum_state.len() as f64 * 0.3).ln();
-        self.cached_depth = Some(depth);
-        self.last_update = chrono::Utc::now();
-        depth
     }
 }
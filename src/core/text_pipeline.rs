@@ -0,0 +1,163 @@
+use std::collections::HashSet;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+pub enum Intent {
+    Greeting,
+    Farewell,
+    Question,
+    Command,
+    Feeling,
+    Statement,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct QueryAnalysis {
+    pub original: String,
+    pub normalized: String,
+    pub tokens: Vec<String>,
+    // Content words, most frequent first (ties by first appearance).
+    pub keywords: Vec<String>,
+    pub intent: Intent,
+    // In [-1, 1]; 0 is neutral.
+    pub sentiment: f64,
+}
+
+const STOPWORDS: &[&str] = &[
+    "a", "about", "after", "all", "am", "an", "and", "any", "are", "as", "at", "be", "been", "but", "by", "can", "could", "did", "do",
+    "does", "for", "from", "had", "has", "have", "he", "her", "him", "his", "how", "i", "if", "in", "into", "is", "it", "its", "just",
+    "me", "my", "of", "on", "or", "our", "please", "she", "so", "some", "than", "that", "the", "their", "them", "then", "there", "these",
+    "they", "this", "to", "us", "was", "we", "were", "what", "when", "where", "which", "who", "why", "will", "with", "would", "you",
+    "your", "not", "no", "very", "really", "tell", "show", "explain", "describe", "give", "list", "help", "hello", "hi", "hey", "bye",
+    "goodbye", "thanks", "thank", "feel", "feeling", "im", "today", "now",
+];
+
+// Whole words, then suffixes (expansions starting with a space) that only apply at a word's end.
+const CONTRACTIONS: &[(&str, &str)] = &[
+    ("can't", "can not"), ("won't", "will not"), ("n't", " not"), ("i'm", "i am"), ("'re", " are"), ("'ve", " have"), ("'ll", " will"), ("'d", " would"),
+];
+
+// Valence lexicon in [-3, 3].
+const LEXICON: &[(&str, f64)] = &[
+    ("good", 2.0), ("great", 3.0), ("love", 3.0), ("like", 1.5), ("happy", 2.5), ("joy", 2.5), ("wonderful", 3.0), ("beautiful", 2.5),
+    ("calm", 1.5), ("curious", 1.0), ("interesting", 1.5), ("thanks", 1.5), ("thank", 1.5), ("nice", 2.0), ("excellent", 3.0), ("hope", 1.5),
+    ("bad", -2.0), ("terrible", -3.0), ("hate", -3.0), ("sad", -2.5), ("angry", -2.5), ("awful", -3.0), ("afraid", -2.0), ("fear", -2.0),
+    ("broken", -2.0), ("fail", -2.0), ("failed", -2.0), ("wrong", -1.5), ("chaos", -0.5), ("lonely", -2.0), ("worried", -2.0), ("boring", -1.5),
+];
+
+const NEGATIONS: &[&str] = &["not", "no", "never", "nothing", "hardly"];
+const INTENSIFIERS: &[(&str, f64)] = &[("very", 1.5), ("really", 1.4), ("so", 1.3), ("extremely", 1.8), ("slightly", 0.5)];
+const QUESTION_WORDS: &[&str] = &["what", "why", "how", "when", "where", "who", "which", "is", "are", "can", "could", "do", "does", "will", "would"];
+const COMMANDS: &[&str] = &["tell", "show", "explain", "describe", "give", "list", "help", "compute", "render", "find"];
+const GREETINGS: &[&str] = &["hello", "hi", "hey", "greetings"];
+const FAREWELLS: &[&str] = &["bye", "goodbye", "farewell"];
+const FEELING_WORDS: &[&str] = &["feel", "feeling", "felt", "am"];
+
+// Lowercases, expands contractions and collapses whitespace.
+pub fn normalize(text: &str) -> String {
+    let lowered = text.to_lowercase().replace(['\u{2019}', '`'], "'");
+    let mut normalized = String::with_capacity(lowered.len());
+    let mut word = String::new();
+    for c in lowered.chars() {
+        if c.is_alphanumeric() || c == '\'' {
+            word.push(c);
+            continue;
+        }
+        normalized += &expand_contraction(&word);
+        word.clear();
+        normalized.push(c);
+    }
+    normalized += &expand_contraction(&word);
+    normalized.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+// Expands a single word, so "o'reilly" keeps its "'re".
+fn expand_contraction(word: &str) -> String {
+    for (contraction, expansion) in CONTRACTIONS {
+        if word == *contraction { return expansion.trim_start().to_string(); }
+        if expansion.starts_with(' ') && word.len() > contraction.len() && word.ends_with(contraction) {
+            return word[..word.len() - contraction.len()].to_string() + expansion;
+        }
+    }
+    word.to_string()
+}
+
+// Splits normalised text into alphanumeric words; other apostrophes are dropped.
+pub fn tokenize(normalized: &str) -> Vec<String> {
+    normalized
+        .split(|c: char| !(c.is_alphanumeric() || c == '\''))
+        .map(|token| token.replace('\'', ""))
+        .filter(|token| !token.is_empty())
+        .collect()
+}
+
+pub fn keywords(tokens: &[String], limit: usize) -> Vec<String> {
+    let mut counts: Vec<(String, usize)> = Vec::new();
+    for token in tokens.iter().filter(|token| token.chars().count() > 1 && !STOPWORDS.contains(&token.as_str())) {
+        match counts.iter_mut().find(|(seen, _)| seen == token) {
+            Some((_, count)) => *count += 1,
+            None => counts.push((token.clone(), 1)),
+        }
+    }
+    // Stable sort keeps first-appearance order among equal counts.
+    counts.sort_by_key(|(_, count)| std::cmp::Reverse(*count));
+    counts.into_iter().take(limit).map(|(token, _)| token).collect()
+}
+
+pub fn intent(original: &str, tokens: &[String]) -> Intent {
+    let first = tokens.first().map(String::as_str).unwrap_or("");
+    let has = |words: &[&str]| tokens.iter().any(|token| words.contains(&token.as_str()));
+    if FAREWELLS.contains(&first) || (tokens.len() <= 3 && has(FAREWELLS)) { return Intent::Farewell; }
+    if GREETINGS.contains(&first) && tokens.len() <= 4 { return Intent::Greeting; }
+    if original.trim_end().ends_with('?') || QUESTION_WORDS.contains(&first) { return Intent::Question; }
+    if COMMANDS.contains(&first) || (first == "please" && tokens.get(1).is_some_and(|t| COMMANDS.contains(&t.as_str()))) { return Intent::Command; }
+    if tokens.windows(2).any(|pair| pair[0] == "i" && FEELING_WORDS.contains(&pair[1].as_str())) && tokens.iter().any(|t| lexicon_score(t).is_some()) {
+        return Intent::Feeling;
+    }
+    if GREETINGS.contains(&first) { return Intent::Greeting; }
+    Intent::Statement
+}
+
+fn lexicon_score(token: &str) -> Option<f64> { LEXICON.iter().find(|(word, _)| *word == token).map(|(_, score)| *score) }
+
+// Lexicon sum with negation (flips the next three words) and intensifiers (scale the next
+// word), squashed by x / sqrt(x^2 + 15) into [-1, 1].
+pub fn sentiment(tokens: &[String]) -> f64 {
+    let mut total = 0.0;
+    let (mut negated_for, mut boost) = (0usize, 1.0);
+    for token in tokens {
+        if NEGATIONS.contains(&token.as_str()) {
+            negated_for = 3;
+            continue;
+        }
+        if let Some((_, factor)) = INTENSIFIERS.iter().find(|(word, _)| word == token) {
+            boost = *factor;
+            continue;
+        }
+        if let Some(score) = lexicon_score(token) {
+            total += if negated_for > 0 { -0.75 * score } else { score } * boost;
+        }
+        boost = 1.0;
+        negated_for = negated_for.saturating_sub(1);
+    }
+    total / (total * total + 15.0).sqrt()
+}
+
+pub fn analyze(query: &str) -> QueryAnalysis {
+    let normalized = normalize(query);
+    let tokens = tokenize(&normalized);
+    QueryAnalysis {
+        original: query.to_string(),
+        keywords: keywords(&tokens, 5),
+        intent: intent(query, &tokens),
+        sentiment: sentiment(&tokens),
+        normalized,
+        tokens,
+    }
+}
+
+// Fraction of `keywords` that appear among `tokens`.
+pub fn keyword_overlap(keywords: &[String], tokens: &[String]) -> f64 {
+    if keywords.is_empty() { return 0.0; }
+    let present: HashSet<&str> = tokens.iter().map(String::as_str).collect();
+    keywords.iter().filter(|keyword| present.contains(keyword.as_str())).count() as f64 / keywords.len() as f64
+}
@@ -0,0 +1,72 @@
+use crate::dynamics::chaotic_systems::{ChaoticSystem, DiscreteMap};
+use crate::dynamics::integrators::AdaptiveRk45;
+use crate::quantum::field_analysis::FieldStatistics;
+
+// How strongly the node's surroundings drive the attractor. The drive only changes how far the
+// trajectory advances per tick, so the signal stays on the attractor whatever the coupling.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Coupling {
+    pub peer_weight: f64,
+    pub field_weight: f64,
+}
+
+impl Coupling {
+    pub fn none() -> Self { Self { peer_weight: 0.0, field_weight: 0.0 } }
+
+    // peer_weight * ln(peers + 1) + field_weight * field entropy (bits), never negative.
+    pub fn drive(&self, peer_count: usize, field: Option<&FieldStatistics>) -> f64 {
+        let peers = (peer_count as f64 + 1.0).ln();
+        let entropy = field.map_or(0.0, |statistics| statistics.shannon_entropy);
+        (self.peer_weight * peers + self.field_weight * entropy).max(0.0)
+    }
+}
+
+// Deterministic chaos signal for `chaos_history`: each tick advances the system by
+// `sample_interval * (1 + drive)` time units (rounded to whole iterations for maps).
+#[derive(Clone)]
+pub struct ChaosSource {
+    system: ChaoticSystem,
+    state: Vec<f64>,
+    time: f64,
+    integrator: AdaptiveRk45,
+    pub sample_interval: f64,
+    pub coupling: Coupling,
+}
+
+impl ChaosSource {
+    pub fn new(system: ChaoticSystem) -> Self {
+        Self {
+            state: system.initial_state(),
+            time: 0.0,
+            integrator: AdaptiveRk45::new(1e-8),
+            sample_interval: if system.is_discrete() { 1.0 } else { 0.05 },
+            coupling: Coupling { peer_weight: 0.1, field_weight: 0.05 },
+            system,
+        }
+    }
+
+    pub fn system(&self) -> ChaoticSystem { self.system }
+
+    pub fn state(&self) -> &[f64] { &self.state }
+
+    pub fn time(&self) -> f64 { self.time }
+
+    // Advances one coupled tick and returns the new state.
+    pub fn step(&mut self, peer_count: usize, field: Option<&FieldStatistics>) -> Vec<f64> {
+        let span = self.sample_interval * (1.0 + self.coupling.drive(peer_count, field));
+        if self.system.is_discrete() {
+            let iterations = span.round().max(1.0);
+            for _ in 0..iterations as usize { self.system.iterate(&mut self.state); }
+            self.time += iterations;
+        } else {
+            self.integrator.integrate(&self.system, self.time, &mut self.state, self.time + span);
+            self.time += span;
+        }
+        self.state.clone()
+    }
+
+    // `samples` uncoupled ticks.
+    pub fn trajectory(&mut self, samples: usize) -> Vec<Vec<f64>> {
+        (0..samples).map(|_| self.step(0, None)).collect()
+    }
+}
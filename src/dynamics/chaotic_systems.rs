@@ -0,0 +1,211 @@
+use crate::dynamics::integrators::OdeSystem;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Lorenz {
+    pub sigma: f64,
+    pub rho: f64,
+    pub beta: f64,
+}
+
+impl Lorenz {
+    pub fn classic() -> Self { Self { sigma: 10.0, rho: 28.0, beta: 8.0 / 3.0 } }
+}
+
+impl OdeSystem for Lorenz {
+    fn dimension(&self) -> usize { 3 }
+
+    fn derivative(&self, _t: f64, s: &[f64], out: &mut [f64]) {
+        out[0] = self.sigma * (s[1] - s[0]);
+        out[1] = s[0] * (self.rho - s[2]) - s[1];
+        out[2] = s[0] * s[1] - self.beta * s[2];
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Rossler {
+    pub a: f64,
+    pub b: f64,
+    pub c: f64,
+}
+
+impl Rossler {
+    pub fn classic() -> Self { Self { a: 0.2, b: 0.2, c: 5.7 } }
+}
+
+impl OdeSystem for Rossler {
+    fn dimension(&self) -> usize { 3 }
+
+    fn derivative(&self, _t: f64, s: &[f64], out: &mut [f64]) {
+        out[0] = -s[1] - s[2];
+        out[1] = s[0] + self.a * s[1];
+        out[2] = self.b + s[2] * (s[0] - self.c);
+    }
+}
+
+// Dimensionless Chua circuit with the piecewise-linear diode slopes `m0` (inner) and `m1` (outer).
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Chua {
+    pub alpha: f64,
+    pub beta: f64,
+    pub m0: f64,
+    pub m1: f64,
+}
+
+impl Chua {
+    pub fn double_scroll() -> Self { Self { alpha: 15.6, beta: 28.0, m0: -1.143, m1: -0.714 } }
+
+    fn diode(&self, x: f64) -> f64 { self.m1 * x + 0.5 * (self.m0 - self.m1) * ((x + 1.0).abs() - (x - 1.0).abs()) }
+}
+
+impl OdeSystem for Chua {
+    fn dimension(&self) -> usize { 3 }
+
+    fn derivative(&self, _t: f64, s: &[f64], out: &mut [f64]) {
+        out[0] = self.alpha * (s[1] - s[0] - self.diode(s[0]));
+        out[1] = s[0] - s[1] + s[2];
+        out[2] = -self.beta * s[1];
+    }
+}
+
+// State is (theta1, omega1, theta2, omega2), angles from the downward vertical.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct DoublePendulum {
+    pub m1: f64,
+    pub m2: f64,
+    pub l1: f64,
+    pub l2: f64,
+    pub g: f64,
+}
+
+impl DoublePendulum {
+    pub fn unit() -> Self { Self { m1: 1.0, m2: 1.0, l1: 1.0, l2: 1.0, g: 9.81 } }
+
+    pub fn energy(&self, s: &[f64]) -> f64 {
+        let (t1, w1, t2, w2) = (s[0], s[1], s[2], s[3]);
+        let kinetic = 0.5 * (self.m1 + self.m2) * self.l1 * self.l1 * w1 * w1
+            + 0.5 * self.m2 * self.l2 * self.l2 * w2 * w2
+            + self.m2 * self.l1 * self.l2 * w1 * w2 * (t1 - t2).cos();
+        let potential = -(self.m1 + self.m2) * self.g * self.l1 * t1.cos() - self.m2 * self.g * self.l2 * t2.cos();
+        kinetic + potential
+    }
+}
+
+impl OdeSystem for DoublePendulum {
+    fn dimension(&self) -> usize { 4 }
+
+    fn derivative(&self, _t: f64, s: &[f64], out: &mut [f64]) {
+        let (t1, w1, t2, w2) = (s[0], s[1], s[2], s[3]);
+        let (m1, m2, l1, l2, g) = (self.m1, self.m2, self.l1, self.l2, self.g);
+        let delta = t1 - t2;
+        let denominator = 2.0 * m1 + m2 - m2 * (2.0 * delta).cos();
+        out[0] = w1;
+        out[1] = (-g * (2.0 * m1 + m2) * t1.sin() - m2 * g * (t1 - 2.0 * t2).sin()
+            - 2.0 * delta.sin() * m2 * (w2 * w2 * l2 + w1 * w1 * l1 * delta.cos()))
+            / (l1 * denominator);
+        out[2] = w2;
+        out[3] = 2.0 * delta.sin() * (w1 * w1 * l1 * (m1 + m2) + g * (m1 + m2) * t1.cos() + w2 * w2 * l2 * m2 * delta.cos())
+            / (l2 * denominator);
+    }
+}
+
+pub trait DiscreteMap {
+    fn dimension(&self) -> usize;
+
+    fn iterate(&self, state: &mut [f64]);
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct LogisticMap {
+    pub r: f64,
+}
+
+impl DiscreteMap for LogisticMap {
+    fn dimension(&self) -> usize { 1 }
+
+    fn iterate(&self, state: &mut [f64]) { state[0] = self.r * state[0] * (1.0 - state[0]); }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct HenonMap {
+    pub a: f64,
+    pub b: f64,
+}
+
+impl HenonMap {
+    pub fn classic() -> Self { Self { a: 1.4, b: 0.3 } }
+}
+
+impl DiscreteMap for HenonMap {
+    fn dimension(&self) -> usize { 2 }
+
+    fn iterate(&self, state: &mut [f64]) {
+        let (x, y) = (state[0], state[1]);
+        state[0] = 1.0 - self.a * x * x + y;
+        state[1] = self.b * x;
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ChaoticSystem {
+    Lorenz(Lorenz),
+    Rossler(Rossler),
+    Chua(Chua),
+    DoublePendulum(DoublePendulum),
+    Logistic(LogisticMap),
+    Henon(HenonMap),
+}
+
+impl ChaoticSystem {
+    pub fn name(&self) -> &'static str {
+        match self {
+            ChaoticSystem::Lorenz(_) => "lorenz",
+            ChaoticSystem::Rossler(_) => "rossler",
+            ChaoticSystem::Chua(_) => "chua",
+            ChaoticSystem::DoublePendulum(_) => "double_pendulum",
+            ChaoticSystem::Logistic(_) => "logistic",
+            ChaoticSystem::Henon(_) => "henon",
+        }
+    }
+
+    pub fn is_discrete(&self) -> bool { matches!(self, ChaoticSystem::Logistic(_) | ChaoticSystem::Henon(_)) }
+
+    // A point on (or quickly attracted to) the system's chaotic attractor.
+    pub fn initial_state(&self) -> Vec<f64> {
+        match self {
+            ChaoticSystem::Lorenz(_) => vec![1.0, 1.0, 1.0],
+            ChaoticSystem::Rossler(_) => vec![1.0, 1.0, 0.0],
+            ChaoticSystem::Chua(_) => vec![0.7, 0.0, 0.0],
+            ChaoticSystem::DoublePendulum(_) => vec![2.0, 0.0, 2.5, 0.0],
+            ChaoticSystem::Logistic(_) => vec![0.2],
+            ChaoticSystem::Henon(_) => vec![0.1, 0.1],
+        }
+    }
+}
+
+impl OdeSystem for ChaoticSystem {
+    fn dimension(&self) -> usize { self.initial_state().len() }
+
+    // Discrete maps have no flow; their derivative is zero.
+    fn derivative(&self, t: f64, state: &[f64], out: &mut [f64]) {
+        match self {
+            ChaoticSystem::Lorenz(system) => system.derivative(t, state, out),
+            ChaoticSystem::Rossler(system) => system.derivative(t, state, out),
+            ChaoticSystem::Chua(system) => system.derivative(t, state, out),
+            ChaoticSystem::DoublePendulum(system) => system.derivative(t, state, out),
+            ChaoticSystem::Logistic(_) | ChaoticSystem::Henon(_) => out.fill(0.0),
+        }
+    }
+}
+
+impl DiscreteMap for ChaoticSystem {
+    fn dimension(&self) -> usize { self.initial_state().len() }
+
+    // Continuous systems are left unchanged; step them with an integrator instead.
+    fn iterate(&self, state: &mut [f64]) {
+        match self {
+            ChaoticSystem::Logistic(map) => map.iterate(state),
+            ChaoticSystem::Henon(map) => map.iterate(state),
+            _ => {}
+        }
+    }
+}
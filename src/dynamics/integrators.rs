@@ -0,0 +1,107 @@
+// Right-hand side of an autonomous or time-dependent ODE system dy/dt = f(t, y).
+pub trait OdeSystem {
+    fn dimension(&self) -> usize;
+
+    fn derivative(&self, t: f64, state: &[f64], out: &mut [f64]);
+}
+
+// Classic fixed-step fourth-order Runge-Kutta.
+#[derive(Clone)]
+pub struct Rk4 {
+    pub dt: f64,
+}
+
+impl Rk4 {
+    pub fn new(dt: f64) -> Self { Self { dt } }
+
+    pub fn step(&self, system: &impl OdeSystem, t: f64, state: &mut [f64]) {
+        let n = system.dimension();
+        let (mut k1, mut k2, mut k3, mut k4, mut probe) = (vec![0.0; n], vec![0.0; n], vec![0.0; n], vec![0.0; n], vec![0.0; n]);
+        let dt = self.dt;
+        system.derivative(t, state, &mut k1);
+        for i in 0..n { probe[i] = state[i] + 0.5 * dt * k1[i]; }
+        system.derivative(t + 0.5 * dt, &probe, &mut k2);
+        for i in 0..n { probe[i] = state[i] + 0.5 * dt * k2[i]; }
+        system.derivative(t + 0.5 * dt, &probe, &mut k3);
+        for i in 0..n { probe[i] = state[i] + dt * k3[i]; }
+        system.derivative(t + dt, &probe, &mut k4);
+        for i in 0..n { state[i] += dt / 6.0 * (k1[i] + 2.0 * k2[i] + 2.0 * k3[i] + k4[i]); }
+    }
+
+    // Steps from `t` to `t_end`, shortening the last step to land exactly on it.
+    pub fn integrate(&self, system: &impl OdeSystem, mut t: f64, state: &mut [f64], t_end: f64) {
+        while t < t_end {
+            let stepper = Rk4::new(self.dt.min(t_end - t));
+            stepper.step(system, t, state);
+            t += stepper.dt;
+        }
+    }
+}
+
+// Dormand-Prince 5(4) with step-size control on the max-norm of the embedded error estimate.
+#[derive(Clone)]
+pub struct AdaptiveRk45 {
+    pub tolerance: f64,
+    pub min_step: f64,
+    pub max_step: f64,
+    // Step size the next call will try first; carried over between calls.
+    pub step: f64,
+    pub rejected_steps: usize,
+}
+
+const C: [f64; 7] = [0.0, 1.0 / 5.0, 3.0 / 10.0, 4.0 / 5.0, 8.0 / 9.0, 1.0, 1.0];
+const A: [[f64; 6]; 7] = [
+    [0.0; 6],
+    [1.0 / 5.0, 0.0, 0.0, 0.0, 0.0, 0.0],
+    [3.0 / 40.0, 9.0 / 40.0, 0.0, 0.0, 0.0, 0.0],
+    [44.0 / 45.0, -56.0 / 15.0, 32.0 / 9.0, 0.0, 0.0, 0.0],
+    [19372.0 / 6561.0, -25360.0 / 2187.0, 64448.0 / 6561.0, -212.0 / 729.0, 0.0, 0.0],
+    [9017.0 / 3168.0, -355.0 / 33.0, 46732.0 / 5247.0, 49.0 / 176.0, -5103.0 / 18656.0, 0.0],
+    [35.0 / 384.0, 0.0, 500.0 / 1113.0, 125.0 / 192.0, -2187.0 / 6784.0, 11.0 / 84.0],
+];
+const B5: [f64; 7] = [35.0 / 384.0, 0.0, 500.0 / 1113.0, 125.0 / 192.0, -2187.0 / 6784.0, 11.0 / 84.0, 0.0];
+const B4: [f64; 7] = [5179.0 / 57600.0, 0.0, 7571.0 / 16695.0, 393.0 / 640.0, -92097.0 / 339200.0, 187.0 / 2100.0, 1.0 / 40.0];
+
+impl AdaptiveRk45 {
+    pub fn new(tolerance: f64) -> Self {
+        Self { tolerance, min_step: 1e-9, max_step: 0.1, step: 0.01, rejected_steps: 0 }
+    }
+
+    // Takes one accepted step of at most `max_dt`, returning its size. Steps at `min_step` are
+    // accepted regardless of the error estimate so integration always makes progress.
+    pub fn step(&mut self, system: &impl OdeSystem, t: f64, state: &mut [f64], max_dt: f64) -> f64 {
+        let n = system.dimension();
+        let mut k = vec![vec![0.0; n]; 7];
+        let mut probe = vec![0.0; n];
+        loop {
+            let h = self.step.clamp(self.min_step, self.max_step).min(max_dt);
+            for stage in 0..7 {
+                for i in 0..n {
+                    probe[i] = state[i] + h * (0..stage).map(|j| A[stage][j] * k[j][i]).sum::<f64>();
+                }
+                system.derivative(t + C[stage] * h, &probe, &mut k[stage]);
+            }
+            let error = (0..n)
+                .map(|i| {
+                    let difference = h * (0..7).map(|j| (B5[j] - B4[j]) * k[j][i]).sum::<f64>();
+                    difference.abs() / (1.0 + state[i].abs())
+                })
+                .fold(0.0, f64::max);
+            let factor = if error > 0.0 { (0.9 * (self.tolerance / error).powf(0.2)).clamp(0.2, 5.0) } else { 5.0 };
+            if error <= self.tolerance || h <= self.min_step {
+                for i in 0..n { state[i] += h * (0..7).map(|j| B5[j] * k[j][i]).sum::<f64>(); }
+                // Only grow from a full step, so a step clipped by `max_dt` doesn't shrink the next one.
+                if h >= self.step.min(self.max_step) { self.step = (h * factor).clamp(self.min_step, self.max_step); }
+                return h;
+            }
+            self.rejected_steps += 1;
+            self.step = h * factor;
+        }
+    }
+
+    pub fn integrate(&mut self, system: &impl OdeSystem, mut t: f64, state: &mut [f64], t_end: f64) {
+        while t_end - t > 1e-12 {
+            t += self.step(system, t, state, t_end - t);
+        }
+    }
+}
@@ -1,3 +1,11 @@
+use std::collections::HashMap;
+use std::sync::{LazyLock, Mutex};
+use crate::core::emotional_contagion::EmotionalSummary;
+
+// Latest emotional summary per sender, per listening recipient. Process-wide, so nodes running
+// on different runtime workers still hear each other.
+static EMOTION_INBOX: LazyLock<Mutex<HashMap<String, HashMap<String, EmotionalSummary>>>> = LazyLock::new(|| Mutex::new(HashMap::new()));
+
 #[derive(Clone, serde::Serialize, serde::Deserialize)]
 pub struct State {
     pub cid: String,
@@ -15,4 +23,32 @@ impl CosmicGossipProtocol {
         let successful_peers = peers.iter().filter(|_| rand::random::<f64>() > churn).count();
         successful_peers >= (replication_factor as f64 * 0.8) as usize
     }
+
+    // Opens an inbox for `peer_id`. Only listening peers are delivered to, so summaries addressed
+    // to peers nobody drains don't pile up.
+    pub fn listen(peer_id: &str) { EMOTION_INBOX.lock().unwrap().entry(peer_id.to_string()).or_default(); }
+
+    // Closes `peer_id`'s inbox, dropping anything undelivered.
+    pub fn stop_listening(peer_id: &str) { EMOTION_INBOX.lock().unwrap().remove(peer_id); }
+
+    // Delivers `summary` to each listening peer independently with probability `1 - churn`,
+    // replacing any older summary from the same sender. Returns how many peers received it.
+    pub async fn gossip_emotion(&self, summary: &EmotionalSummary, peers: &[String], churn: f64) -> usize {
+        let mut inbox = EMOTION_INBOX.lock().unwrap();
+        let mut delivered = 0;
+        for peer in peers.iter().filter(|peer| **peer != summary.peer_id) {
+            let Some(received) = inbox.get_mut(peer) else { continue };
+            if rand::random::<f64>() < churn { continue; }
+            if received.get(&summary.peer_id).is_none_or(|older| older.timestamp_millis <= summary.timestamp_millis) {
+                received.insert(summary.peer_id.clone(), summary.clone());
+            }
+            delivered += 1;
+        }
+        delivered
+    }
+
+    // Removes and returns every summary delivered to `peer_id` since the last call.
+    pub fn take_emotions(peer_id: &str) -> Vec<EmotionalSummary> {
+        EMOTION_INBOX.lock().unwrap().get_mut(peer_id).map(|received| std::mem::take(received).into_values().collect()).unwrap_or_default()
+    }
 }
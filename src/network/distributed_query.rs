@@ -0,0 +1,161 @@
+use std::collections::{HashMap, HashSet};
+use std::future::Future;
+use std::sync::{Arc, LazyLock, Mutex};
+use std::time::Duration;
+use tokio::task::JoinSet;
+use tokio::time::Instant;
+use crate::core::coherence::CoherenceScorer;
+use crate::core::response_generators::GenerationFuture;
+use crate::core::text_pipeline::{self, QueryAnalysis};
+use crate::network::local_api::QueryRequest;
+
+// Answers a query on behalf of a peer.
+pub type Responder = Arc<dyn Fn(QueryRequest) -> GenerationFuture<'static> + Send + Sync>;
+
+// Registered responders by peer id. Process-wide, so a fan-out running on any runtime worker
+// reaches peers registered from another.
+static RESPONDERS: LazyLock<Mutex<HashMap<String, Responder>>> = LazyLock::new(|| Mutex::new(HashMap::new()));
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum CombineStrategy {
+    // The consensus answer: the candidate with the highest summed token-set Jaccard similarity
+    // to every other candidate.
+    Vote,
+    // The candidate the coherence scorer rates highest.
+    Coherence,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct FanOutConfig {
+    pub k: usize,
+    // Per-peer deadline; late answers are dropped and the peer listed as timed out.
+    pub timeout: Duration,
+    pub strategy: CombineStrategy,
+    // Under `Vote`, candidates at least this similar to the winner count as supporting it.
+    pub agreement: f64,
+}
+
+impl FanOutConfig {
+    pub fn new(k: usize) -> Self { Self { k, timeout: Duration::from_millis(500), strategy: CombineStrategy::Coherence, agreement: 0.5 } }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Candidate {
+    pub peer_id: String,
+    pub response: String,
+    // Vote support or coherence total, depending on the strategy.
+    pub score: f64,
+    pub latency: Duration,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Provenance {
+    pub strategy: CombineStrategy,
+    // Every peer that answered in time, the local node first, in the order they were asked.
+    pub candidates: Vec<Candidate>,
+    pub chosen: String,
+    // Peers whose answers agree with the chosen one (`Vote`), or just the chosen peer.
+    pub supporters: Vec<String>,
+    pub timed_out: Vec<String>,
+    // Peers without a registered responder.
+    pub unreachable: Vec<String>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct DistributedAnswer {
+    pub response: String,
+    pub provenance: Provenance,
+}
+
+#[derive(Clone)]
+pub struct QueryFanOut;
+
+impl QueryFanOut {
+    pub fn register(peer_id: &str, responder: Responder) { RESPONDERS.lock().unwrap().insert(peer_id.to_string(), responder); }
+
+    pub fn unregister(peer_id: &str) { RESPONDERS.lock().unwrap().remove(peer_id); }
+
+    // The subset of `peers` that can answer queries, in the same order.
+    pub fn reachable(peers: &[String]) -> Vec<String> {
+        let responders = RESPONDERS.lock().unwrap();
+        peers.iter().filter(|peer| responders.contains_key(*peer)).cloned().collect()
+    }
+
+    // Asks every peer (in session `peer:<origin>`) while `local` answers for `origin`, then
+    // combines all answers.
+    pub async fn fan_out(origin: &str, query: &str, local: impl Future<Output = String>, peers: &[String], config: &FanOutConfig) -> DistributedAnswer {
+        let started = Instant::now();
+        let mut pending = JoinSet::new();
+        let mut unreachable = Vec::new();
+        for (index, peer) in peers.iter().enumerate() {
+            let Some(responder) = RESPONDERS.lock().unwrap().get(peer).cloned() else {
+                unreachable.push(peer.clone());
+                continue;
+            };
+            let request = QueryRequest { session_id: format!("peer:{}", origin), query: query.to_string(), depth: 0 };
+            let answer = responder(request);
+            let timeout = config.timeout;
+            pending.spawn(async move { (index, tokio::time::timeout(timeout, answer).await.ok(), started.elapsed()) });
+        }
+        let gather = async {
+            let (mut answered, mut timed_out) = (Vec::new(), Vec::new());
+            while let Some(joined) = pending.join_next().await {
+                // A panicking responder is treated like one that never answered.
+                let Ok((index, response, latency)) = joined else { continue };
+                match response {
+                    Some(response) => answered.push((index, response, latency)),
+                    None => timed_out.push(index),
+                }
+            }
+            (answered, timed_out)
+        };
+        let (local_response, (mut answered, mut timed_out)) = tokio::join!(local, gather);
+        answered.sort_by_key(|(index, _, _)| *index);
+        timed_out.sort();
+        let mut candidates = vec![Candidate { peer_id: origin.to_string(), response: local_response, score: 0.0, latency: Duration::ZERO }];
+        candidates.extend(answered.into_iter().map(|(index, response, latency)| Candidate { peer_id: peers[index].clone(), response, score: 0.0, latency }));
+        let analysis = text_pipeline::analyze(query);
+        let (chosen, supporters) = combine(&mut candidates, &analysis, config);
+        DistributedAnswer {
+            response: candidates[chosen].response.clone(),
+            provenance: Provenance {
+                strategy: config.strategy,
+                chosen: candidates[chosen].peer_id.clone(),
+                supporters,
+                candidates,
+                timed_out: timed_out.into_iter().map(|index| peers[index].clone()).collect(),
+                unreachable,
+            },
+        }
+    }
+}
+
+// Scores every candidate and returns the winner's index plus its supporters. Ties go to the
+// earlier candidate, so the local answer wins when nothing beats it.
+fn combine(candidates: &mut [Candidate], analysis: &QueryAnalysis, config: &FanOutConfig) -> (usize, Vec<String>) {
+    match config.strategy {
+        CombineStrategy::Coherence => {
+            let scorer = CoherenceScorer::new();
+            for candidate in candidates.iter_mut() { candidate.score = scorer.score(analysis, &candidate.response).total; }
+            let chosen = best(candidates);
+            (chosen, vec![candidates[chosen].peer_id.clone()])
+        }
+        CombineStrategy::Vote => {
+            let token_sets: Vec<HashSet<String>> = candidates.iter().map(|candidate| text_pipeline::tokenize(&text_pipeline::normalize(&candidate.response)).into_iter().collect()).collect();
+            let similarity = |a: usize, b: usize| {
+                let union = token_sets[a].union(&token_sets[b]).count();
+                if union == 0 { 1.0 } else { token_sets[a].intersection(&token_sets[b]).count() as f64 / union as f64 }
+            };
+            for i in 0..candidates.len() {
+                candidates[i].score = (0..candidates.len()).filter(|j| *j != i).map(|j| similarity(i, j)).sum();
+            }
+            let chosen = best(candidates);
+            let supporters = (0..candidates.len()).filter(|j| *j == chosen || similarity(chosen, *j) >= config.agreement).map(|j| candidates[j].peer_id.clone()).collect();
+            (chosen, supporters)
+        }
+    }
+}
+
+fn best(candidates: &[Candidate]) -> usize {
+    (0..candidates.len()).fold(0, |best, i| if candidates[i].score > candidates[best].score { i } else { best })
+}
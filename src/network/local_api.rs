@@ -0,0 +1,157 @@
+use std::sync::Arc;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio_stream::StreamExt;
+use crate::core::dialogue_sessions::DEFAULT_SESSION;
+use crate::core::response_stream::{ResponseEvent, ResponseStream};
+use crate::storage::dialogue_export::ExportFormat;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct QueryRequest {
+    pub session_id: String,
+    pub query: String,
+    pub depth: usize,
+}
+
+pub type QueryHandler = Arc<dyn Fn(QueryRequest) -> ResponseStream + Send + Sync>;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ExportRequest {
+    pub session_id: Option<String>,
+    pub search: Option<String>,
+    pub limit: usize,
+    pub format: ExportFormat,
+}
+
+pub type ExportHandler = Arc<dyn Fn(ExportRequest) -> String + Send + Sync>;
+
+// Minimal HTTP/1.1 front end for local clients. `GET /query?q=...&session=...&depth=...` relays
+// the response stream as server-sent events when the client accepts `text/event-stream`, and as
+// chunked newline-delimited JSON otherwise. With an exporter, `GET /export?session=...&search=...
+// &limit=...&format=json|csv` returns the dialogue log. One request per connection.
+#[derive(Clone)]
+pub struct LocalApi {
+    handler: QueryHandler,
+    exporter: Option<ExportHandler>,
+}
+
+impl LocalApi {
+    pub fn new(handler: impl Fn(QueryRequest) -> ResponseStream + Send + Sync + 'static) -> Self { Self { handler: Arc::new(handler), exporter: None } }
+
+    pub fn with_exporter(mut self, exporter: impl Fn(ExportRequest) -> String + Send + Sync + 'static) -> Self {
+        self.exporter = Some(Arc::new(exporter));
+        self
+    }
+
+    pub async fn serve(self, listener: TcpListener) {
+        loop {
+            let Ok((socket, _)) = listener.accept().await else { continue };
+            tokio::spawn(self.clone().handle(socket));
+        }
+    }
+
+    async fn handle(self, mut socket: TcpStream) {
+        let mut request = Vec::new();
+        let mut buffer = [0u8; 4096];
+        while !request.windows(4).any(|window| window == b"\r\n\r\n") {
+            match socket.read(&mut buffer).await {
+                Ok(0) | Err(_) => return,
+                Ok(read) => request.extend_from_slice(&buffer[..read]),
+            }
+            if request.len() > 64 * 1024 { return; }
+        }
+        let request = String::from_utf8_lossy(&request).to_string();
+        if let (Some(exporter), Some(export)) = (&self.exporter, parse_export_request(&request)) {
+            let content_type = if export.format == ExportFormat::Csv { "text/csv" } else { "application/json" };
+            let body = exporter(export);
+            let response = format!("HTTP/1.1 200 OK\r\ncontent-type: {}\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{}", content_type, body.len(), body);
+            let _ = socket.write_all(response.as_bytes()).await;
+            return;
+        }
+        let Some(query) = parse_request(&request) else {
+            let _ = socket.write_all(b"HTTP/1.1 404 Not Found\r\ncontent-length: 0\r\nconnection: close\r\n\r\n").await;
+            return;
+        };
+        let sse = request.lines().any(|line| line.to_lowercase().starts_with("accept:") && line.contains("text/event-stream"));
+        let content_type = if sse { "text/event-stream" } else { "application/x-ndjson" };
+        let head = format!("HTTP/1.1 200 OK\r\ncontent-type: {}\r\ncache-control: no-cache\r\ntransfer-encoding: chunked\r\nconnection: close\r\n\r\n", content_type);
+        if socket.write_all(head.as_bytes()).await.is_err() { return; }
+        let mut events = (self.handler)(query);
+        while let Some(event) = events.next().await {
+            let body = if sse { sse_event(&event) } else { event.to_json() + "\n" };
+            if socket.write_all(chunk(&body).as_bytes()).await.is_err() { return; }
+        }
+        let _ = socket.write_all(b"0\r\n\r\n").await;
+    }
+}
+
+// The query string of a `GET` request for `path`.
+fn get_query_string<'a>(request: &'a str, path: &str) -> Option<&'a str> {
+    let mut parts = request.lines().next()?.split(' ');
+    if parts.next()? != "GET" { return None; }
+    let target = parts.next()?;
+    let (target_path, query_string) = target.split_once('?').unwrap_or((target, ""));
+    (target_path == path).then_some(query_string)
+}
+
+fn query_pairs(query_string: &str) -> impl Iterator<Item = (&str, &str)> {
+    query_string.split('&').map(|pair| pair.split_once('=').unwrap_or((pair, "")))
+}
+
+// `None` unless the request line is `GET /query?...` with a non-empty `q`.
+pub fn parse_request(request: &str) -> Option<QueryRequest> {
+    let query_string = get_query_string(request, "/query")?;
+    let mut parsed = QueryRequest { session_id: DEFAULT_SESSION.to_string(), query: String::new(), depth: 0 };
+    for (key, value) in query_pairs(query_string) {
+        match key {
+            "q" => parsed.query = percent_decode(value),
+            "session" => parsed.session_id = percent_decode(value),
+            // Refinement never goes past depth 15, so larger requests gain nothing.
+            "depth" => parsed.depth = value.parse::<usize>().ok()?.min(15),
+            _ => {}
+        }
+    }
+    (!parsed.query.trim().is_empty()).then_some(parsed)
+}
+
+// `None` unless the request line is `GET /export?...` with a known `format` (JSON by default).
+// Empty `session` and `search` values mean every session and no search.
+pub fn parse_export_request(request: &str) -> Option<ExportRequest> {
+    let query_string = get_query_string(request, "/export")?;
+    let mut parsed = ExportRequest { session_id: None, search: None, limit: 100, format: ExportFormat::Json };
+    for (key, value) in query_pairs(query_string) {
+        let value = percent_decode(value);
+        match key {
+            "session" => parsed.session_id = Some(value).filter(|session| !session.is_empty()),
+            "search" => parsed.search = Some(value).filter(|search| !search.trim().is_empty()),
+            "limit" => parsed.limit = value.parse().ok()?,
+            "format" => parsed.format = ExportFormat::from_name(&value)?,
+            _ => {}
+        }
+    }
+    Some(parsed)
+}
+
+pub fn sse_event(event: &ResponseEvent) -> String { format!("event: {}\ndata: {}\n\n", event.name(), event.to_json()) }
+
+fn chunk(body: &str) -> String { format!("{:x}\r\n{}\r\n", body.len(), body) }
+
+// Decodes `%XX` escapes and `+` as space; malformed escapes are kept verbatim.
+fn percent_decode(value: &str) -> String {
+    let bytes = value.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let escaped = bytes.get(i + 1..i + 3).and_then(|hex| u8::from_str_radix(std::str::from_utf8(hex).ok()?, 16).ok());
+        match (bytes[i], escaped) {
+            (b'+', _) => decoded.push(b' '),
+            (b'%', Some(byte)) => {
+                decoded.push(byte);
+                i += 2;
+            }
+            (byte, _) => decoded.push(byte),
+        }
+        i += 1;
+    }
+    String::from_utf8_lossy(&decoded).to_string()
+}
@@ -13,6 +13,12 @@ impl NetworkMetrics {
         score
     }
 
+    // Log-scaled fraction of the 5M-node target currently reachable, in [0, 1].
+    pub fn health(&self, node: &SelfEvolvingFractalGossipNode) -> f64 {
+        let peer_count = node.active_nodes.load(Ordering::Relaxed) as f64;
+        ((peer_count + 1.0).ln() / 5_000_001f64.ln()).clamp(0.0, 1.0)
+    }
+
     pub fn throughput(&self, _node: &SelfEvolvingFractalGossipNode, queries: usize, seconds: f64) -> f64 {
         queries as f64 / seconds
     }
@@ -1,3 +1,10 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::sync::{LazyLock, Mutex};
+
+// The observed peer graph as adjacency sets. Process-wide, so links recorded by a sync loop on
+// one runtime worker are seen by estimators and rankings running on any other.
+static LINKS: LazyLock<Mutex<HashMap<String, HashSet<String>>>> = LazyLock::new(|| Mutex::new(HashMap::new()));
+
 #[derive(Clone)]
 pub struct PeerDiscovery;
 
@@ -11,6 +18,45 @@ impl PeerDiscovery {
         Self::LOCAL_NODES.with(|nodes| nodes.borrow_mut().push(peer_id));
     }
 
+    // Records undirected links from `peer_id` to each of `peers` in the observed peer graph.
+    pub fn record_links(peer_id: &str, peers: &[String]) {
+        let mut links = LINKS.lock().unwrap();
+        for peer in peers.iter().filter(|p| *p != peer_id) {
+            links.entry(peer_id.to_string()).or_default().insert(peer.clone());
+            links.entry(peer.clone()).or_default().insert(peer_id.to_string());
+        }
+    }
+
+    // Degree of every node in the observed peer graph, in no particular order.
+    pub fn degree_sequence() -> Vec<usize> {
+        LINKS.lock().unwrap().values().map(|neighbors| neighbors.len()).collect()
+    }
+
+    // The `k` candidates nearest `peer_id`: fewest hops in the observed peer graph first, then
+    // (and for unlinked candidates) smallest XOR distance between hashed ids.
+    pub fn closest_peers(peer_id: &str, candidates: &[String], k: usize) -> Vec<String> {
+        let hops = {
+            let links = LINKS.lock().unwrap();
+            let mut hops = HashMap::from([(peer_id.to_string(), 0usize)]);
+            let mut frontier = VecDeque::from([peer_id.to_string()]);
+            while let Some(current) = frontier.pop_front() {
+                let next = hops[&current] + 1;
+                for neighbor in links.get(&current).into_iter().flatten() {
+                    if !hops.contains_key(neighbor) {
+                        hops.insert(neighbor.clone(), next);
+                        frontier.push_back(neighbor.clone());
+                    }
+                }
+            }
+            hops
+        };
+        let hash = |id: &str| id.bytes().fold(0xCBF2_9CE4_8422_2325u64, |hash, byte| (hash ^ byte as u64).wrapping_mul(0x0000_0100_0000_01B3));
+        let mut ranked: Vec<&String> = candidates.iter().filter(|candidate| *candidate != peer_id).collect();
+        ranked.sort_by_key(|candidate| (hops.get(candidate.as_str()).copied().unwrap_or(usize::MAX), hash(candidate) ^ hash(peer_id)));
+        ranked.dedup();
+        ranked.into_iter().take(k).cloned().collect()
+    }
+
     pub fn connect_global() {
         Self::GLOBAL_PEERS.with(|peers| {
             let mut peers = peers.borrow_mut();
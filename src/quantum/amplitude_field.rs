@@ -0,0 +1,125 @@
+use num_complex::Complex64;
+use rand::rngs::StdRng;
+use rand::Rng;
+use crate::quantum::field_tensor::{row_major_strides, unravel, FieldTensor};
+
+// Half-open box `[start, end)` of cells.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Region<const D: usize> {
+    pub start: [usize; D],
+    pub end: [usize; D],
+}
+
+impl<const D: usize> Region<D> {
+    pub fn new(start: [usize; D], end: [usize; D]) -> Self { Self { start, end } }
+
+    pub fn whole(shape: [usize; D]) -> Self { Self { start: [0; D], end: shape } }
+
+    pub fn contains(&self, coords: [usize; D]) -> bool {
+        (0..D).all(|axis| self.start[axis] <= coords[axis] && coords[axis] < self.end[axis])
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum MeasurementOutcome<const D: usize> {
+    // The state collapsed onto `coords`, which had probability `probability` before measuring.
+    Found { coords: [usize; D], probability: f64 },
+    // Nothing was detected; the region was projected out and the rest renormalised.
+    NotFound { region_probability: f64 },
+}
+
+// Complex amplitudes over the same lattice as the tensor field. Evolution steps are unitary
+// (phase rotations and disjoint 2x2 Hadamard mixes), so the total probability stays 1 up to
+// rounding, which `normalize` removes.
+#[derive(Clone, Debug, PartialEq)]
+pub struct AmplitudeField<const D: usize> {
+    shape: [usize; D],
+    amplitudes: Vec<Complex64>,
+}
+
+impl<const D: usize> AmplitudeField<D> {
+    pub fn uniform(shape: [usize; D]) -> Self {
+        let cells: usize = shape.iter().product();
+        let amplitude = Complex64::new(1.0 / (cells.max(1) as f64).sqrt(), 0.0);
+        Self { shape, amplitudes: vec![amplitude; cells] }
+    }
+
+    // |psi| = sqrt(|value|) with a phase of pi for negative values; falls back to uniform for an all-zero field.
+    pub fn from_real(field: &FieldTensor<D>) -> Self {
+        let amplitudes: Vec<Complex64> = field.as_slice().iter().map(|v| Complex64::new(v.signum() * v.abs().sqrt(), 0.0)).collect();
+        if amplitudes.iter().all(|a| a.norm_sqr() == 0.0) { return Self::uniform(field.shape()); }
+        let mut state = Self { shape: field.shape(), amplitudes };
+        state.normalize();
+        state
+    }
+
+    pub fn shape(&self) -> [usize; D] { self.shape }
+
+    pub fn amplitudes(&self) -> &[Complex64] { &self.amplitudes }
+
+    pub fn total_probability(&self) -> f64 { self.amplitudes.iter().map(|a| a.norm_sqr()).sum() }
+
+    pub fn normalize(&mut self) {
+        let norm = self.total_probability().sqrt();
+        if norm > 0.0 {
+            for amplitude in &mut self.amplitudes { *amplitude /= norm; }
+        }
+    }
+
+    pub fn probabilities(&self) -> FieldTensor<D> {
+        FieldTensor::from_data(self.shape, self.amplitudes.iter().map(|a| a.norm_sqr()).collect())
+    }
+
+    // psi <- exp(-i * V * dt) psi, with the real field as the potential V.
+    pub fn phase_rotation(&mut self, potential: &FieldTensor<D>, dt: f64) { self.phase_rotation_at(0, potential.as_slice(), dt); }
+
+    // Rotates only the cells from flat index `offset` on, e.g. the layers one shard owns.
+    pub fn phase_rotation_at(&mut self, offset: usize, potential: &[f64], dt: f64) {
+        for (amplitude, v) in self.amplitudes[offset..].iter_mut().zip(potential) {
+            *amplitude *= Complex64::from_polar(1.0, -v * dt);
+        }
+    }
+
+    // Applies H = [[1, 1], [1, -1]] / sqrt(2) to neighbouring pairs along `axis`, pairing cells
+    // (2k + parity, 2k + 1 + parity); alternating parity lets amplitude spread across the lattice.
+    pub fn hadamard_mix(&mut self, axis: usize, parity: usize) {
+        let stride = row_major_strides(self.shape)[axis];
+        let extent = self.shape[axis];
+        for index in 0..self.amplitudes.len() {
+            let c = unravel(self.shape, index)[axis];
+            if c < parity || (c - parity) % 2 == 1 || c + 1 >= extent { continue; }
+            let (a, b) = (self.amplitudes[index], self.amplitudes[index + stride]);
+            self.amplitudes[index] = (a + b) * std::f64::consts::FRAC_1_SQRT_2;
+            self.amplitudes[index + stride] = (a - b) * std::f64::consts::FRAC_1_SQRT_2;
+        }
+    }
+
+    // Projective position measurement restricted to `region`, drawing from `rng`. A state with
+    // no probability anywhere is left alone and reports nothing found.
+    pub fn measure(&mut self, region: &Region<D>, rng: &mut StdRng) -> MeasurementOutcome<D> {
+        let total = self.total_probability();
+        if total <= 0.0 { return MeasurementOutcome::NotFound { region_probability: 0.0 }; }
+        let inside: Vec<usize> = (0..self.amplitudes.len()).filter(|&index| region.contains(unravel(self.shape, index))).collect();
+        let region_probability = inside.iter().map(|&index| self.amplitudes[index].norm_sqr()).sum::<f64>() / total;
+        let mut draw = rng.gen::<f64>();
+        if draw >= region_probability {
+            for &index in &inside { self.amplitudes[index] = Complex64::new(0.0, 0.0); }
+            self.normalize();
+            return MeasurementOutcome::NotFound { region_probability };
+        }
+        let mut chosen = *inside.last().expect("region with non-zero probability has cells");
+        for &index in &inside {
+            let probability = self.amplitudes[index].norm_sqr() / total;
+            if draw < probability {
+                chosen = index;
+                break;
+            }
+            draw -= probability;
+        }
+        let probability = self.amplitudes[chosen].norm_sqr() / total;
+        let phase = self.amplitudes[chosen].arg();
+        self.amplitudes.iter_mut().for_each(|a| *a = Complex64::new(0.0, 0.0));
+        self.amplitudes[chosen] = Complex64::from_polar(1.0, phase);
+        MeasurementOutcome::Found { coords: unravel(self.shape, chosen), probability }
+    }
+}
@@ -1,11 +1,17 @@
 use crate::core::self_evolving_fractal_gossip_node::SelfEvolvingFractalGossipNode;
+use crate::network::peer_discovery::PeerDiscovery;
+use crate::quantum::entropy_estimators::EntropyInput;
 
 pub struct CosmicEntropy;
 
 impl CosmicEntropy {
+    // Entropy of the node under its configured estimator; the unit depends on the estimator
+    // (see `EntropyEstimator::unit`). The default reads only the tensor field, so the value
+    // no longer feeds back on the chaos history it is stored in.
     pub fn calculate(node: &SelfEvolvingFractalGossipNode) -> f64 {
-        let chaos_sum = node.chaos_history.last().map_or(0.0, |v| v.iter().sum());
-        let peer_factor = (node.active_nodes.load(Ordering::Relaxed) as f64 + 1.0).log2();
-        chaos_sum * peer_factor
+        let series = node.chaos_history.totals();
+        let peer_degrees = PeerDiscovery::degree_sequence();
+        let input = EntropyInput { field: node.tensor_engine.tensor_field.as_slice(), series: &series, peer_degrees: &peer_degrees };
+        node.entropy_config.lock().unwrap().build().estimate(&input)
     }
 }
@@ -0,0 +1,158 @@
+use std::collections::HashMap;
+use crate::quantum::field_analysis::Histogram;
+
+#[derive(Clone, Copy, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+pub enum EntropyUnit {
+    Bits,
+    Nats,
+    // Divided by the estimator's maximum, so in [0, 1].
+    Normalized,
+}
+
+// Everything an estimator may look at; each one documents which part it reads.
+#[derive(Clone, Copy, Default)]
+pub struct EntropyInput<'a> {
+    pub field: &'a [f64],
+    pub series: &'a [f64],
+    pub peer_degrees: &'a [usize],
+}
+
+pub trait EntropyEstimator: Send + Sync {
+    fn name(&self) -> &'static str;
+    fn unit(&self) -> EntropyUnit;
+    fn estimate(&self, input: &EntropyInput<'_>) -> f64;
+}
+
+// Shannon entropy of the tensor field's value histogram (`input.field`), in bits. Ranges from 0
+// for a constant field to log2(bins) for values spread evenly across the bins.
+#[derive(Clone)]
+pub struct ShannonFieldEntropy {
+    pub bins: usize,
+}
+
+impl EntropyEstimator for ShannonFieldEntropy {
+    fn name(&self) -> &'static str { "shannon_field" }
+    fn unit(&self) -> EntropyUnit { EntropyUnit::Bits }
+    fn estimate(&self, input: &EntropyInput<'_>) -> f64 { Histogram::new(input.field, self.bins).shannon_entropy() }
+}
+
+// Sample entropy (Richman & Moorman) of the chaos time series (`input.series`), in nats:
+// -ln(A / B), where B counts pairs of length-`m` templates within tolerance r = r_factor * std
+// and A the pairs that still match at length m + 1. Low for regular signals, high for noise.
+// When no matches exist the estimate is capped at its largest resolvable value. Pair counting is
+// quadratic, so only the newest `SAMPLE_ENTROPY_WINDOW` samples are read: at most ~131k template
+// comparisons per estimate however long the history grows.
+pub const SAMPLE_ENTROPY_WINDOW: usize = 512;
+
+#[derive(Clone)]
+pub struct SampleEntropy {
+    pub m: usize,
+    pub r_factor: f64,
+}
+
+impl EntropyEstimator for SampleEntropy {
+    fn name(&self) -> &'static str { "sample_entropy" }
+    fn unit(&self) -> EntropyUnit { EntropyUnit::Nats }
+
+    fn estimate(&self, input: &EntropyInput<'_>) -> f64 {
+        let series = &input.series[input.series.len().saturating_sub(SAMPLE_ENTROPY_WINDOW)..];
+        let (m, n) = (self.m.max(1), series.len());
+        if n <= m + 1 { return 0.0; }
+        let mean = series.iter().sum::<f64>() / n as f64;
+        let std = (series.iter().map(|x| (x - mean) * (x - mean)).sum::<f64>() / n as f64).sqrt();
+        let r = self.r_factor * std;
+        let templates = n - m;
+        let matches = |length: usize| {
+            let mut count = 0u64;
+            for i in 0..templates {
+                for j in i + 1..templates {
+                    if (0..length).all(|k| (series[i + k] - series[j + k]).abs() <= r) { count += 1; }
+                }
+            }
+            count
+        };
+        let (b, a) = (matches(m), matches(m + 1));
+        let bound = ((templates * (templates - 1)) as f64 / 2.0).ln();
+        if a == 0 || b == 0 { return bound; }
+        -((a as f64) / (b as f64)).ln()
+    }
+}
+
+// Permutation entropy (Bandt & Pompe) of the chaos time series (`input.series`): Shannon
+// entropy of the ordinal patterns of `order` samples spaced `delay` apart. In bits, or divided
+// by log2(order!) when `normalized`, so a monotonic series scores 0 and white noise about 1.
+#[derive(Clone)]
+pub struct PermutationEntropy {
+    pub order: usize,
+    pub delay: usize,
+    pub normalized: bool,
+}
+
+impl EntropyEstimator for PermutationEntropy {
+    fn name(&self) -> &'static str { "permutation_entropy" }
+    fn unit(&self) -> EntropyUnit { if self.normalized { EntropyUnit::Normalized } else { EntropyUnit::Bits } }
+
+    fn estimate(&self, input: &EntropyInput<'_>) -> f64 {
+        let (order, delay) = (self.order.max(2), self.delay.max(1));
+        let span = (order - 1) * delay;
+        if input.series.len() <= span { return 0.0; }
+        let mut patterns: HashMap<Vec<usize>, usize> = HashMap::new();
+        for start in 0..input.series.len() - span {
+            let mut pattern: Vec<usize> = (0..order).collect();
+            pattern.sort_by(|&a, &b| input.series[start + a * delay].total_cmp(&input.series[start + b * delay]));
+            *patterns.entry(pattern).or_insert(0) += 1;
+        }
+        let bits = shannon_bits(patterns.values().copied());
+        if self.normalized {
+            let max = (2..=order).map(|k| (k as f64).log2()).sum::<f64>();
+            bits / max
+        } else {
+            bits
+        }
+    }
+}
+
+// Shannon entropy of the peer graph's degree distribution (`input.peer_degrees`), in bits:
+// 0 when every node has the same degree, growing as connectivity becomes heterogeneous.
+#[derive(Clone)]
+pub struct DegreeEntropy;
+
+impl EntropyEstimator for DegreeEntropy {
+    fn name(&self) -> &'static str { "degree_entropy" }
+    fn unit(&self) -> EntropyUnit { EntropyUnit::Bits }
+
+    fn estimate(&self, input: &EntropyInput<'_>) -> f64 {
+        let mut distribution: HashMap<usize, usize> = HashMap::new();
+        for degree in input.peer_degrees { *distribution.entry(*degree).or_insert(0) += 1; }
+        shannon_bits(distribution.values().copied())
+    }
+}
+
+fn shannon_bits(counts: impl Iterator<Item = usize> + Clone) -> f64 {
+    let total: usize = counts.clone().sum();
+    if total == 0 { return 0.0; }
+    counts.filter(|&c| c > 0).map(|c| { let p = c as f64 / total as f64; -p * p.log2() }).sum()
+}
+
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+pub enum EntropyConfig {
+    ShannonField { bins: usize },
+    SampleEntropy { m: usize, r_factor: f64 },
+    PermutationEntropy { order: usize, delay: usize, normalized: bool },
+    DegreeEntropy,
+}
+
+impl EntropyConfig {
+    pub fn build(&self) -> Box<dyn EntropyEstimator> {
+        match self.clone() {
+            EntropyConfig::ShannonField { bins } => Box::new(ShannonFieldEntropy { bins }),
+            EntropyConfig::SampleEntropy { m, r_factor } => Box::new(SampleEntropy { m, r_factor }),
+            EntropyConfig::PermutationEntropy { order, delay, normalized } => Box::new(PermutationEntropy { order, delay, normalized }),
+            EntropyConfig::DegreeEntropy => Box::new(DegreeEntropy),
+        }
+    }
+}
+
+impl Default for EntropyConfig {
+    fn default() -> Self { EntropyConfig::ShannonField { bins: 32 } }
+}
@@ -0,0 +1,281 @@
+use rustfft::num_complex::Complex64;
+use rustfft::FftPlanner;
+use crate::quantum::field_tensor::{row_major_strides, unravel, FieldTensor};
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Histogram {
+    pub min: f64,
+    pub max: f64,
+    pub counts: Vec<usize>,
+}
+
+impl Histogram {
+    pub fn new(values: &[f64], bins: usize) -> Self {
+        let min = values.iter().cloned().fold(f64::INFINITY, f64::min);
+        let max = values.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+        let bins = bins.max(1);
+        let mut counts = vec![0; bins];
+        if values.is_empty() { return Self { min: 0.0, max: 0.0, counts }; }
+        let width = (max - min) / bins as f64;
+        for value in values {
+            let bin = if width > 0.0 { ((value - min) / width) as usize } else { 0 };
+            counts[bin.min(bins - 1)] += 1;
+        }
+        Self { min, max, counts }
+    }
+
+    // -sum(p log2 p) over the bins, in bits.
+    pub fn shannon_entropy(&self) -> f64 {
+        let total: usize = self.counts.iter().sum();
+        if total == 0 { return 0.0; }
+        self.counts
+            .iter()
+            .filter(|&&count| count > 0)
+            .map(|&count| {
+                let p = count as f64 / total as f64;
+                -p * p.log2()
+            })
+            .sum()
+    }
+}
+
+// Snapshot of the field after an update, cheap enough to compute every tick.
+#[derive(Clone, Debug, PartialEq)]
+pub struct FieldStatistics {
+    pub mean: f64,
+    pub variance: f64,
+    pub min: f64,
+    pub max: f64,
+    pub histogram: Histogram,
+    // Shannon entropy of `histogram`, in bits.
+    pub shannon_entropy: f64,
+    // Periodic autocorrelation at lags 1..=max_lag, averaged over the axes.
+    pub autocorrelation: Vec<f64>,
+    // Radially averaged power |F(k)|^2 / N of the mean-removed field, indexed by integer |k|.
+    pub power_spectrum: Vec<f64>,
+    // Box-counting dimension of the cells above the mean.
+    pub box_counting_dimension: f64,
+    // Mean log growth per update of the distance between successive fields; `None` until three
+    // fields have been seen. Negative while the field settles, positive while its steps grow.
+    // It follows a single trajectory, so it says nothing about how perturbed states separate;
+    // see `lyapunov_exponent` for that.
+    pub log_step_growth: Option<f64>,
+    // Largest Lyapunov exponent per update from a perturbed twin of the field (see
+    // `LyapunovEstimator`); `None` unless the engine tracks one.
+    pub lyapunov_exponent: Option<f64>,
+}
+
+impl FieldStatistics {
+    // White noise has near-zero autocorrelation and a flat spectrum; structure shows up as
+    // persistent correlation at lag 1.
+    pub fn looks_structured(&self) -> bool {
+        self.variance > 0.0 && self.autocorrelation.first().is_some_and(|r| *r > 0.2)
+    }
+}
+
+#[derive(Clone)]
+pub struct FieldAnalyzer<const D: usize> {
+    pub bins: usize,
+    pub max_lag: usize,
+    previous: Option<FieldTensor<D>>,
+    previous_distance: Option<f64>,
+    log_growth_sum: f64,
+    log_growth_samples: usize,
+}
+
+impl<const D: usize> FieldAnalyzer<D> {
+    pub fn new(bins: usize, max_lag: usize) -> Self {
+        Self {
+            bins,
+            max_lag,
+            previous: None,
+            previous_distance: None,
+            log_growth_sum: 0.0,
+            log_growth_samples: 0,
+        }
+    }
+
+    // Analyses `field` and folds it into the running step-growth estimate.
+    pub fn observe(&mut self, field: &FieldTensor<D>) -> FieldStatistics {
+        self.track_step_growth(field);
+        let mut statistics = self.analyze(field);
+        statistics.log_step_growth = (self.log_growth_samples > 0).then(|| self.log_growth_sum / self.log_growth_samples as f64);
+        statistics
+    }
+
+    // Stateless statistics of a single field (no step-growth estimate).
+    pub fn analyze(&self, field: &FieldTensor<D>) -> FieldStatistics {
+        let values = field.as_slice();
+        let n = values.len().max(1) as f64;
+        let mean = values.iter().sum::<f64>() / n;
+        let variance = values.iter().map(|v| (v - mean) * (v - mean)).sum::<f64>() / n;
+        let histogram = Histogram::new(values, self.bins);
+        FieldStatistics {
+            mean,
+            variance,
+            min: histogram.min,
+            max: histogram.max,
+            shannon_entropy: histogram.shannon_entropy(),
+            histogram,
+            autocorrelation: autocorrelation(field, mean, variance, self.max_lag),
+            power_spectrum: self.power_spectrum(field, mean),
+            box_counting_dimension: box_counting_dimension(field, mean),
+            log_step_growth: None,
+            lyapunov_exponent: None,
+        }
+    }
+
+    fn track_step_growth(&mut self, field: &FieldTensor<D>) {
+        if let Some(previous) = self.previous.as_ref().filter(|previous| previous.shape() == field.shape()) {
+            let distance = field.as_slice().iter().zip(previous.as_slice()).map(|(a, b)| (a - b) * (a - b)).sum::<f64>().sqrt();
+            if let Some(before) = self.previous_distance.filter(|before| *before > 0.0 && distance > 0.0) {
+                self.log_growth_sum += (distance / before).ln();
+                self.log_growth_samples += 1;
+            }
+            self.previous_distance = Some(distance);
+        }
+        self.previous = Some(field.clone());
+    }
+
+    fn power_spectrum(&self, field: &FieldTensor<D>, mean: f64) -> Vec<f64> {
+        let shape = field.shape();
+        let strides = row_major_strides(shape);
+        let mut buffer: Vec<Complex64> = field.as_slice().iter().map(|v| Complex64::new(v - mean, 0.0)).collect();
+        if buffer.is_empty() { return Vec::new(); }
+        let mut planner = FftPlanner::new();
+        for axis in 0..D {
+            let (extent, stride) = (shape[axis], strides[axis]);
+            let fft = planner.plan_fft_forward(extent);
+            let mut line = vec![Complex64::new(0.0, 0.0); extent];
+            for start in (0..buffer.len()).filter(|&index| unravel(shape, index)[axis] == 0) {
+                for (k, value) in line.iter_mut().enumerate() { *value = buffer[start + k * stride]; }
+                fft.process(&mut line);
+                for (k, value) in line.iter().enumerate() { buffer[start + k * stride] = *value; }
+            }
+        }
+        let radius = |index: usize| {
+            let coords = unravel(shape, index);
+            (0..D).map(|axis| { let k = coords[axis].min(shape[axis] - coords[axis]) as f64; k * k }).sum::<f64>().sqrt().round() as usize
+        };
+        let max_radius = (0..buffer.len()).map(radius).max().unwrap_or(0);
+        let (mut power, mut counts) = (vec![0.0; max_radius + 1], vec![0usize; max_radius + 1]);
+        for (index, value) in buffer.iter().enumerate() {
+            let r = radius(index);
+            power[r] += value.norm_sqr() / buffer.len() as f64;
+            counts[r] += 1;
+        }
+        power.iter().zip(counts).map(|(p, c)| if c > 0 { p / c as f64 } else { 0.0 }).collect()
+    }
+}
+
+// Benettin estimate of the largest Lyapunov exponent: a twin trajectory starts `epsilon` from the
+// reference; after every step their separation d adds ln(d / epsilon) to a running mean and the
+// twin is pulled back to distance `epsilon` along the same direction.
+#[derive(Clone, Debug)]
+pub struct LyapunovEstimator {
+    pub epsilon: f64,
+    log_growth_sum: f64,
+    steps: usize,
+}
+
+impl LyapunovEstimator {
+    pub fn new(epsilon: f64) -> Self { Self { epsilon, log_growth_sum: 0.0, steps: 0 } }
+
+    // `reference` offset by `epsilon` along a fixed pseudo-random direction, so the twin
+    // overlaps every mode of the dynamics.
+    pub fn perturb(&self, reference: &[f64]) -> Vec<f64> {
+        let direction: Vec<f64> = (0..reference.len() as u64).map(|index| splitmix64(index) as f64 / u64::MAX as f64 * 2.0 - 1.0).collect();
+        let norm = direction.iter().map(|d| d * d).sum::<f64>().sqrt().max(f64::MIN_POSITIVE);
+        reference.iter().zip(&direction).map(|(value, d)| value + d * self.epsilon / norm).collect()
+    }
+
+    // Records one step's separation and returns the factor to scale the twin's offset by. A twin
+    // that landed exactly on the reference has no direction left to follow and is not counted.
+    pub fn record(&mut self, separation: f64) -> f64 {
+        if !(separation > 0.0 && separation.is_finite()) { return 1.0; }
+        self.log_growth_sum += (separation / self.epsilon).ln();
+        self.steps += 1;
+        self.epsilon / separation
+    }
+
+    pub fn steps(&self) -> usize { self.steps }
+
+    pub fn exponent(&self) -> Option<f64> { (self.steps > 0).then(|| self.log_growth_sum / self.steps as f64) }
+}
+
+// Largest Lyapunov exponent per step of the map `step`, starting from `field` and averaging over
+// `steps` renormalised steps.
+pub fn lyapunov_exponent<const D: usize>(field: &FieldTensor<D>, steps: usize, epsilon: f64, mut step: impl FnMut(&FieldTensor<D>) -> FieldTensor<D>) -> Option<f64> {
+    let mut estimator = LyapunovEstimator::new(epsilon);
+    let mut reference = field.clone();
+    let mut twin = FieldTensor::from_data(field.shape(), estimator.perturb(field.as_slice()));
+    for _ in 0..steps {
+        reference = step(&reference);
+        twin = step(&twin);
+        let separation = twin.as_slice().iter().zip(reference.as_slice()).map(|(t, r)| (t - r) * (t - r)).sum::<f64>().sqrt();
+        let scale = estimator.record(separation);
+        for (t, r) in twin.as_mut_slice().iter_mut().zip(reference.as_slice()) { *t = r + (*t - r) * scale; }
+    }
+    estimator.exponent()
+}
+
+fn splitmix64(index: u64) -> u64 {
+    let mut z = index.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+fn autocorrelation<const D: usize>(field: &FieldTensor<D>, mean: f64, variance: f64, max_lag: usize) -> Vec<f64> {
+    let shape = field.shape();
+    let strides = row_major_strides(shape);
+    let values = field.as_slice();
+    (1..=max_lag)
+        .map(|lag| {
+            if variance <= 0.0 { return 0.0; }
+            let per_axis: f64 = (0..D)
+                .map(|axis| {
+                    let (extent, stride) = (shape[axis], strides[axis]);
+                    let covariance: f64 = (0..values.len())
+                        .map(|index| {
+                            let c = unravel(shape, index)[axis];
+                            let shifted = index - c * stride + ((c + lag) % extent) * stride;
+                            (values[index] - mean) * (values[shifted] - mean)
+                        })
+                        .sum();
+                    covariance / values.len() as f64 / variance
+                })
+                .sum();
+            per_axis / D as f64
+        })
+        .collect()
+}
+
+// Slope of log N(s) against log(1/s) for box sizes s = 1, 2, 4, ... up to half the smallest extent.
+fn box_counting_dimension<const D: usize>(field: &FieldTensor<D>, threshold: f64) -> f64 {
+    let shape = field.shape();
+    let smallest = shape.iter().copied().min().unwrap_or(0);
+    let mut points = Vec::new();
+    let mut size = 1;
+    while size <= (smallest / 2).max(1) {
+        let boxes_shape = shape.map(|n| n.div_ceil(size));
+        let box_strides = row_major_strides(boxes_shape);
+        let mut occupied = vec![false; boxes_shape.iter().product()];
+        for (index, value) in field.as_slice().iter().enumerate() {
+            if *value > threshold {
+                let coords = unravel(shape, index);
+                occupied[(0..D).map(|axis| coords[axis] / size * box_strides[axis]).sum::<usize>()] = true;
+            }
+        }
+        let count = occupied.iter().filter(|o| **o).count();
+        if count > 0 { points.push(((1.0 / size as f64).ln(), (count as f64).ln())); }
+        if smallest < 2 { break; }
+        size *= 2;
+    }
+    if points.len() < 2 { return 0.0; }
+    let n = points.len() as f64;
+    let (mx, my) = (points.iter().map(|p| p.0).sum::<f64>() / n, points.iter().map(|p| p.1).sum::<f64>() / n);
+    let covariance: f64 = points.iter().map(|(x, y)| (x - mx) * (y - my)).sum();
+    let spread: f64 = points.iter().map(|(x, _)| (x - mx) * (x - mx)).sum();
+    covariance / spread
+}
@@ -0,0 +1,356 @@
+use crate::quantum::field_tensor::FieldTensor;
+
+// Local update rules for the tensor field. Each tick every cell is recomputed from its own
+// state and its neighbours' previous state across all of the rule's channels; channel 0 is
+// what the engine exposes as `tensor_field`.
+pub trait FieldRule<const D: usize>: Send + Sync {
+    fn name(&self) -> &'static str;
+
+    fn channels(&self) -> usize { 1 }
+
+    fn initial_value(&self, _channel: usize, _position: [f64; D], _noise: f64) -> f64 { 0.0 }
+
+    // Writes the cell's next value for every channel into `out`.
+    fn update(&self, cell: &Neighborhood<'_, D>, out: &mut [f64]);
+
+    // Optional vectorisable fast path for a whole run of cells along the last axis (only used
+    // when `D >= 2`). `out` holds `row.len()` values per channel, one channel after another.
+    // Must agree with `update`; return false to fall back to per-cell updates.
+    fn update_row(&self, _row: &RowStencil<'_, D>, _out: &mut [f64]) -> bool { false }
+}
+
+// A contiguous run of cells along the last axis (which wraps) with the matching rows of its
+// face-adjacent neighbours, laid out so kernels compile to straight-line slice loops. Borrows
+// everything from the shard, so building one allocates nothing.
+pub struct RowStencil<'a, const D: usize> {
+    channels: &'a [FieldTensor<D>],
+    start: usize,
+    // Starts of the previous/next rows along every axis but the last, in axis order.
+    neighbor_starts: &'a [usize],
+    pub noise: &'a [f64],
+}
+
+impl<'a, const D: usize> RowStencil<'a, D> {
+    pub fn new(channels: &'a [FieldTensor<D>], start: usize, neighbor_starts: &'a [usize], noise: &'a [f64]) -> Self {
+        Self { channels, start, neighbor_starts, noise }
+    }
+
+    pub fn len(&self) -> usize { self.noise.len() }
+
+    pub fn is_empty(&self) -> bool { self.noise.is_empty() }
+
+    pub fn center(&self, channel: usize) -> &'a [f64] { self.row(channel, self.start) }
+
+    pub fn neighbor_rows(&self, channel: usize) -> impl Iterator<Item = &'a [f64]> + '_ {
+        self.neighbor_starts.iter().map(move |start| self.row(channel, *start))
+    }
+
+    fn row(&self, channel: usize, start: usize) -> &'a [f64] { &self.channels[channel].as_slice()[start..start + self.len()] }
+
+    // Same sum order as `Neighborhood::laplacian`, so both paths give identical results.
+    pub fn laplacian_into(&self, channel: usize, out: &mut [f64]) {
+        let center = self.center(channel);
+        let n = center.len();
+        let dimensions = self.neighbor_starts.len() / 2 + 1;
+        out.fill(0.0);
+        for neighbor in self.neighbor_rows(channel) {
+            for (o, v) in out.iter_mut().zip(neighbor.iter()) { *o += v; }
+        }
+        out[0] += center[n - 1];
+        for (o, v) in out[1..].iter_mut().zip(&center[..n - 1]) { *o += v; }
+        for (o, v) in out[..n - 1].iter_mut().zip(&center[1..]) { *o += v; }
+        out[n - 1] += center[0];
+        let k = 2.0 * dimensions as f64;
+        for (o, v) in out.iter_mut().zip(center.iter()) { *o -= k * v; }
+    }
+}
+
+// Maps integer cell coordinates onto [-1, 1] per axis, sampling cell centers.
+pub fn normalized_position<const D: usize>(coords: [usize; D], shape: [usize; D]) -> [f64; D] {
+    let mut position = [0.0; D];
+    for axis in 0..D {
+        position[axis] = (2 * coords[axis] + 1) as f64 / shape[axis] as f64 - 1.0;
+    }
+    position
+}
+
+pub struct Neighborhood<'a, const D: usize> {
+    channels: &'a [FieldTensor<D>],
+    shape: [usize; D],
+    strides: [usize; D],
+    index: usize,
+    coords: [usize; D],
+    position: [f64; D],
+    noise: f64,
+}
+
+impl<'a, const D: usize> Neighborhood<'a, D> {
+    // `channels` hold a slab with one halo layer on each side of axis 0, which is never wrapped;
+    // `coords` are the cell's coordinates within that slab.
+    pub fn new(channels: &'a [FieldTensor<D>], shape: [usize; D], strides: [usize; D], index: usize, coords: [usize; D], position: [f64; D], noise: f64) -> Self {
+        Self { channels, shape, strides, index, coords, position, noise }
+    }
+
+    pub fn value(&self, channel: usize) -> f64 { self.channels[channel].as_slice()[self.index] }
+
+    // Normalised coordinates of the cell in the whole field, each in [-1, 1].
+    pub fn position(&self) -> [f64; D] { self.position }
+
+    // Deterministic per-cell, per-tick noise in [0, 1).
+    pub fn noise(&self) -> f64 { self.noise }
+
+    // The 2 * D face-adjacent (von Neumann) neighbours.
+    pub fn neighbors(&self, channel: usize) -> impl Iterator<Item = f64> + '_ {
+        let data = self.channels[channel].as_slice();
+        (0..D).flat_map(move |axis| [self.shifted(axis, -1), self.shifted(axis, 1)]).map(move |index| data[index])
+    }
+
+    pub fn laplacian(&self, channel: usize) -> f64 {
+        self.neighbors(channel).sum::<f64>() - 2.0 * D as f64 * self.value(channel)
+    }
+
+    // Counts the 3^D - 1 surrounding (Moore) cells whose value is at least `threshold`.
+    pub fn live_neighbors(&self, channel: usize, threshold: f64) -> usize {
+        let data = self.channels[channel].as_slice();
+        let mut live = 0;
+        for code in 0..3usize.pow(D as u32) {
+            let mut index = self.index;
+            let mut digits = code;
+            let mut is_center = true;
+            for axis in 0..D {
+                let step = (digits % 3) as isize - 1;
+                digits /= 3;
+                if step != 0 {
+                    is_center = false;
+                    index = self.wrap(index, axis, step);
+                }
+            }
+            if !is_center && data[index] >= threshold { live += 1; }
+        }
+        live
+    }
+
+    fn shifted(&self, axis: usize, step: isize) -> usize { self.wrap(self.index, axis, step) }
+
+    fn wrap(&self, index: usize, axis: usize, step: isize) -> usize {
+        let stride = self.strides[axis];
+        if axis == 0 {
+            return if step < 0 { index - stride } else { index + stride };
+        }
+        let (n, c) = (self.shape[axis], self.coords[axis]);
+        let moved = (c as isize + step).rem_euclid(n as isize) as usize;
+        index - c * stride + moved * stride
+    }
+}
+
+// The original transform: decay toward zero, a little diffusion, and noise.
+#[derive(Clone)]
+pub struct QuantumDecay {
+    pub decay: f64,
+    pub coupling: f64,
+    pub noise: f64,
+}
+
+impl QuantumDecay {
+    pub fn new() -> Self { Self { decay: 0.5, coupling: 0.1, noise: 0.15 } }
+}
+
+impl<const D: usize> FieldRule<D> for QuantumDecay {
+    fn name(&self) -> &'static str { "quantum_decay" }
+
+    fn update(&self, cell: &Neighborhood<'_, D>, out: &mut [f64]) {
+        out[0] = cell.value(0) * self.decay + cell.laplacian(0) * self.coupling + (cell.noise() * 0.16 - 0.08) * self.noise;
+    }
+
+    fn update_row(&self, row: &RowStencil<'_, D>, out: &mut [f64]) -> bool {
+        row.laplacian_into(0, out);
+        for ((o, v), n) in out.iter_mut().zip(row.center(0)).zip(row.noise) {
+            *o = v * self.decay + *o * self.coupling + (n * 0.16 - 0.08) * self.noise;
+        }
+        true
+    }
+}
+
+// Explicit heat equation; stable while `rate <= 1 / (2D)`.
+#[derive(Clone)]
+pub struct Diffusion {
+    pub rate: f64,
+    pub noise: f64,
+}
+
+impl Diffusion {
+    pub fn new(rate: f64) -> Self { Self { rate, noise: 0.0 } }
+}
+
+impl<const D: usize> FieldRule<D> for Diffusion {
+    fn name(&self) -> &'static str { "diffusion" }
+
+    fn initial_value(&self, _channel: usize, position: [f64; D], _noise: f64) -> f64 {
+        let r2: f64 = position.iter().map(|p| p * p).sum();
+        (-r2 * 20.0).exp()
+    }
+
+    fn update(&self, cell: &Neighborhood<'_, D>, out: &mut [f64]) {
+        out[0] = cell.value(0) + self.rate * cell.laplacian(0) + (cell.noise() - 0.5) * self.noise;
+    }
+
+    fn update_row(&self, row: &RowStencil<'_, D>, out: &mut [f64]) -> bool {
+        row.laplacian_into(0, out);
+        for ((o, v), n) in out.iter_mut().zip(row.center(0)).zip(row.noise) {
+            *o = v + self.rate * *o + (n - 0.5) * self.noise;
+        }
+        true
+    }
+}
+
+// Gray-Scott reaction-diffusion. Channel 0 is the activator V, channel 1 the substrate U.
+#[derive(Clone)]
+pub struct GrayScott {
+    pub diffusion_u: f64,
+    pub diffusion_v: f64,
+    pub feed: f64,
+    pub kill: f64,
+    pub dt: f64,
+}
+
+impl GrayScott {
+    // Pearson's "mitosis" regime, which forms self-replicating spots.
+    pub fn new() -> Self { Self { diffusion_u: 0.16, diffusion_v: 0.08, feed: 0.035, kill: 0.065, dt: 1.0 } }
+}
+
+impl<const D: usize> FieldRule<D> for GrayScott {
+    fn name(&self) -> &'static str { "gray_scott" }
+
+    fn channels(&self) -> usize { 2 }
+
+    fn initial_value(&self, channel: usize, position: [f64; D], noise: f64) -> f64 {
+        let seeded = position.iter().all(|p| p.abs() < 0.1);
+        match (channel, seeded) {
+            (0, true) => 0.25 + noise * 0.05,
+            (0, false) => 0.0,
+            (_, true) => 0.5,
+            _ => 1.0,
+        }
+    }
+
+    fn update(&self, cell: &Neighborhood<'_, D>, out: &mut [f64]) {
+        let (v, u) = (cell.value(0), cell.value(1));
+        let reaction = u * v * v;
+        out[0] = v + self.dt * (self.diffusion_v * cell.laplacian(0) + reaction - (self.feed + self.kill) * v);
+        out[1] = u + self.dt * (self.diffusion_u * cell.laplacian(1) - reaction + self.feed * (1.0 - u));
+    }
+
+    fn update_row(&self, row: &RowStencil<'_, D>, out: &mut [f64]) -> bool {
+        let (next_v, next_u) = out.split_at_mut(row.len());
+        row.laplacian_into(0, next_v);
+        row.laplacian_into(1, next_u);
+        let (vs, us) = (row.center(0), row.center(1));
+        for (((nv, nu), v), u) in next_v.iter_mut().zip(next_u.iter_mut()).zip(vs).zip(us) {
+            let reaction = u * v * v;
+            *nv = v + self.dt * (self.diffusion_v * *nv + reaction - (self.feed + self.kill) * v);
+            *nu = u + self.dt * (self.diffusion_u * *nu - reaction + self.feed * (1.0 - u));
+        }
+        true
+    }
+}
+
+// Coupled map lattice of logistic maps: x' = (1 - e) f(x) + e * mean(f(neighbours)).
+#[derive(Clone)]
+pub struct CoupledLogisticLattice {
+    pub r: f64,
+    pub epsilon: f64,
+}
+
+impl CoupledLogisticLattice {
+    pub fn new() -> Self { Self { r: 3.9, epsilon: 0.3 } }
+
+    fn logistic(&self, x: f64) -> f64 { self.r * x * (1.0 - x) }
+}
+
+impl<const D: usize> FieldRule<D> for CoupledLogisticLattice {
+    fn name(&self) -> &'static str { "coupled_logistic_lattice" }
+
+    fn initial_value(&self, _channel: usize, _position: [f64; D], noise: f64) -> f64 { noise }
+
+    fn update(&self, cell: &Neighborhood<'_, D>, out: &mut [f64]) {
+        let neighbor_mean = cell.neighbors(0).map(|x| self.logistic(x)).sum::<f64>() / (2 * D) as f64;
+        out[0] = (1.0 - self.epsilon) * self.logistic(cell.value(0)) + self.epsilon * neighbor_mean;
+    }
+}
+
+// Outer-totalistic "life-like" automaton over Moore neighbourhoods; cells are alive at >= 0.5.
+#[derive(Clone)]
+pub struct LifeLike {
+    pub birth: Vec<usize>,
+    pub survive: Vec<usize>,
+    pub initial_density: f64,
+}
+
+impl LifeLike {
+    // Conway's B3/S23.
+    pub fn conway() -> Self { Self { birth: vec![3], survive: vec![2, 3], initial_density: 0.3 } }
+}
+
+impl<const D: usize> FieldRule<D> for LifeLike {
+    fn name(&self) -> &'static str { "life_like" }
+
+    fn initial_value(&self, _channel: usize, _position: [f64; D], noise: f64) -> f64 {
+        if noise < self.initial_density { 1.0 } else { 0.0 }
+    }
+
+    fn update(&self, cell: &Neighborhood<'_, D>, out: &mut [f64]) {
+        let live = cell.live_neighbors(0, 0.5);
+        let alive = cell.value(0) >= 0.5;
+        let next = if alive { self.survive.contains(&live) } else { self.birth.contains(&live) };
+        out[0] = if next { 1.0 } else { 0.0 };
+    }
+}
+
+// Iterates z -> z^2 + c once per tick in every cell, with the first two axes mapped onto the
+// complex plane. Without `julia` each cell's c is its position (Mandelbrot); with it c is fixed
+// and z starts at the position. Channel 0 rises toward 1 while the orbit stays bounded and
+// freezes once it escapes; channels 1 and 2 hold Re z and Im z.
+#[derive(Clone)]
+pub struct ComplexIteratedMap {
+    pub center: (f64, f64),
+    pub scale: f64,
+    pub julia: Option<(f64, f64)>,
+}
+
+impl ComplexIteratedMap {
+    pub fn mandelbrot() -> Self { Self { center: (-0.5, 0.0), scale: 1.5, julia: None } }
+
+    pub fn julia(c: (f64, f64)) -> Self { Self { center: (0.0, 0.0), scale: 1.5, julia: Some(c) } }
+
+    fn plane_point<const D: usize>(&self, position: [f64; D]) -> (f64, f64) {
+        let y = if D > 1 { position[1] } else { 0.0 };
+        (self.center.0 + position[0] * self.scale, self.center.1 + y * self.scale)
+    }
+}
+
+impl<const D: usize> FieldRule<D> for ComplexIteratedMap {
+    fn name(&self) -> &'static str { "complex_iterated_map" }
+
+    fn channels(&self) -> usize { 3 }
+
+    fn initial_value(&self, channel: usize, position: [f64; D], _noise: f64) -> f64 {
+        let point = self.plane_point(position);
+        match (channel, self.julia) {
+            (1, Some(_)) => point.0,
+            (2, Some(_)) => point.1,
+            _ => 0.0,
+        }
+    }
+
+    fn update(&self, cell: &Neighborhood<'_, D>, out: &mut [f64]) {
+        let (level, re, im) = (cell.value(0), cell.value(1), cell.value(2));
+        if re * re + im * im > 4.0 {
+            out.copy_from_slice(&[level, re, im]);
+            return;
+        }
+        let c = self.julia.unwrap_or_else(|| self.plane_point(cell.position()));
+        out[0] = level + (1.0 - level) * 0.05;
+        out[1] = re * re - im * im + c.0;
+        out[2] = 2.0 * re * im + c.1;
+    }
+}
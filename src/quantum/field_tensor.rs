@@ -0,0 +1,215 @@
+use std::ops::{Index, IndexMut};
+
+// Dense `D`-dimensional field stored contiguously in row-major order (last axis fastest).
+#[derive(Clone, Debug, PartialEq)]
+pub struct FieldTensor<const D: usize> {
+    shape: [usize; D],
+    data: Vec<f64>,
+}
+
+impl<const D: usize> FieldTensor<D> {
+    pub fn zeros(shape: [usize; D]) -> Self {
+        Self { shape, data: vec![0.0; shape.iter().product()] }
+    }
+
+    pub fn from_data(shape: [usize; D], data: Vec<f64>) -> Self {
+        assert_eq!(shape.iter().product::<usize>(), data.len(), "data length does not match shape {:?}", shape);
+        Self { shape, data }
+    }
+
+    pub fn shape(&self) -> [usize; D] { self.shape }
+
+    pub fn len(&self) -> usize { self.data.len() }
+
+    pub fn is_empty(&self) -> bool { self.data.is_empty() }
+
+    pub fn as_slice(&self) -> &[f64] { &self.data }
+
+    pub fn as_mut_slice(&mut self) -> &mut [f64] { &mut self.data }
+
+    pub fn into_data(self) -> Vec<f64> { self.data }
+
+    pub fn strides(&self) -> [usize; D] { row_major_strides(self.shape) }
+
+    // Cells in one layer along axis 0 (a row in 2D, a YZ plane in 3D).
+    pub fn layer_len(&self) -> usize { self.shape[1..].iter().product() }
+
+    pub fn offset(&self, coords: [usize; D]) -> usize {
+        coords.iter().zip(self.strides()).map(|(c, s)| c * s).sum()
+    }
+
+    pub fn coords(&self, offset: usize) -> [usize; D] { unravel(self.shape, offset) }
+
+    pub fn view(&self) -> FieldView<'_, D> {
+        FieldView { data: &self.data, shape: self.shape, strides: self.strides(), offset: 0 }
+    }
+
+    pub fn view_mut(&mut self) -> FieldViewMut<'_, D> {
+        let strides = self.strides();
+        FieldViewMut { shape: self.shape, strides, offset: 0, data: &mut self.data }
+    }
+
+    pub fn center_coords(&self) -> [usize; D] { self.shape.map(|n| n / 2) }
+
+    pub fn center(&self) -> f64 { self[self.center_coords()] }
+
+    // The 2D cross-section spanned by `axis_a` x `axis_b` through `at`. Axes at or beyond `D`
+    // are treated as having extent 1, so a 2D field still yields XZ/YZ strips.
+    pub fn plane(&self, axis_a: usize, axis_b: usize, at: [usize; D]) -> Plane {
+        let extent = |axis: usize| if axis < D { self.shape[axis] } else { 1 };
+        let (width, height) = (extent(axis_a), extent(axis_b));
+        let mut coords = at;
+        let mut values = Vec::with_capacity(width * height);
+        for a in 0..width {
+            if axis_a < D { coords[axis_a] = a; }
+            for b in 0..height {
+                if axis_b < D { coords[axis_b] = b; }
+                values.push(self[coords]);
+            }
+        }
+        Plane { width, height, values }
+    }
+}
+
+impl<const D: usize> Index<[usize; D]> for FieldTensor<D> {
+    type Output = f64;
+    fn index(&self, coords: [usize; D]) -> &f64 { &self.data[self.offset(coords)] }
+}
+
+impl<const D: usize> IndexMut<[usize; D]> for FieldTensor<D> {
+    fn index_mut(&mut self, coords: [usize; D]) -> &mut f64 {
+        let offset = self.offset(coords);
+        &mut self.data[offset]
+    }
+}
+
+// Borrowed window into a field with explicit strides, so sub-ranges and permuted axes can be
+// read without copying. Fixing an axis keeps `D` and leaves that axis with extent 1.
+#[derive(Clone, Copy)]
+pub struct FieldView<'a, const D: usize> {
+    data: &'a [f64],
+    shape: [usize; D],
+    strides: [usize; D],
+    offset: usize,
+}
+
+impl<'a, const D: usize> FieldView<'a, D> {
+    pub fn shape(&self) -> [usize; D] { self.shape }
+
+    pub fn strides(&self) -> [usize; D] { self.strides }
+
+    pub fn len(&self) -> usize { self.shape.iter().product() }
+
+    pub fn is_empty(&self) -> bool { self.len() == 0 }
+
+    pub fn offset_of(&self, coords: [usize; D]) -> usize {
+        self.offset + coords.iter().zip(self.strides).map(|(c, s)| c * s).sum::<usize>()
+    }
+
+    pub fn get(&self, coords: [usize; D]) -> f64 { self.data[self.offset_of(coords)] }
+
+    // Restricts `axis` to `range`.
+    pub fn narrow(&self, axis: usize, range: std::ops::Range<usize>) -> Self {
+        assert!(range.end <= self.shape[axis], "range {:?} out of bounds for axis {} of extent {}", range, axis, self.shape[axis]);
+        let mut view = *self;
+        view.offset += range.start * self.strides[axis];
+        view.shape[axis] = range.len();
+        view
+    }
+
+    pub fn fix(&self, axis: usize, index: usize) -> Self { self.narrow(axis, index..index + 1) }
+
+    pub fn permuted(&self, axes: [usize; D]) -> Self {
+        let mut view = *self;
+        for (to, from) in axes.iter().enumerate() {
+            view.shape[to] = self.shape[*from];
+            view.strides[to] = self.strides[*from];
+        }
+        view
+    }
+
+    // True when the last axis is unit-stride, so `row` can hand out plain slices.
+    pub fn rows_contiguous(&self) -> bool { D == 0 || self.strides[D - 1] == 1 || self.shape[D - 1] <= 1 }
+
+    // The run along the last axis through `coords` (its last coordinate is ignored).
+    pub fn row(&self, coords: [usize; D]) -> &'a [f64] {
+        assert!(self.rows_contiguous(), "rows of a permuted view are not contiguous");
+        let mut start = coords;
+        start[D - 1] = 0;
+        let begin = self.offset_of(start);
+        &self.data[begin..begin + self.shape[D - 1]]
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = f64> + '_ {
+        (0..self.len()).map(move |flat| self.get(unravel(self.shape, flat)))
+    }
+
+    pub fn to_tensor(&self) -> FieldTensor<D> { FieldTensor::from_data(self.shape, self.iter().collect()) }
+}
+
+pub struct FieldViewMut<'a, const D: usize> {
+    data: &'a mut [f64],
+    shape: [usize; D],
+    strides: [usize; D],
+    offset: usize,
+}
+
+impl<'a, const D: usize> FieldViewMut<'a, D> {
+    pub fn shape(&self) -> [usize; D] { self.shape }
+
+    pub fn as_view(&self) -> FieldView<'_, D> {
+        FieldView { data: self.data, shape: self.shape, strides: self.strides, offset: self.offset }
+    }
+
+    pub fn narrow(self, axis: usize, range: std::ops::Range<usize>) -> Self {
+        assert!(range.end <= self.shape[axis], "range {:?} out of bounds for axis {} of extent {}", range, axis, self.shape[axis]);
+        let mut view = self;
+        view.offset += range.start * view.strides[axis];
+        view.shape[axis] = range.len();
+        view
+    }
+
+    pub fn row_mut(&mut self, coords: [usize; D]) -> &mut [f64] {
+        assert!(self.as_view().rows_contiguous(), "rows of a permuted view are not contiguous");
+        let mut start = coords;
+        start[D - 1] = 0;
+        let begin = self.as_view().offset_of(start);
+        &mut self.data[begin..begin + self.shape[D - 1]]
+    }
+
+    pub fn fill(&mut self, value: f64) {
+        for flat in 0..self.shape.iter().product() {
+            let offset = self.as_view().offset_of(unravel(self.shape, flat));
+            self.data[offset] = value;
+        }
+    }
+}
+
+pub fn row_major_strides<const D: usize>(shape: [usize; D]) -> [usize; D] {
+    let mut strides = [1; D];
+    for axis in (0..D.saturating_sub(1)).rev() {
+        strides[axis] = strides[axis + 1] * shape[axis + 1];
+    }
+    strides
+}
+
+// Row-major coordinates of the `flat`-th cell of `shape`.
+pub fn unravel<const D: usize>(shape: [usize; D], mut flat: usize) -> [usize; D] {
+    let mut coords = [0; D];
+    for axis in (0..D).rev() {
+        coords[axis] = flat % shape[axis];
+        flat /= shape[axis];
+    }
+    coords
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Plane {
+    pub width: usize,
+    pub height: usize,
+    pub values: Vec<f64>,
+}
+
+impl Plane {
+    pub fn get(&self, a: usize, b: usize) -> f64 { self.values[a * self.height + b] }
+}
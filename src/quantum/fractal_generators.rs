@@ -0,0 +1,175 @@
+use num_complex::Complex64;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use crate::quantum::field_tensor::FieldTensor;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum FractalKind {
+    Mandelbrot,
+    Julia,
+    BurningShip,
+    Newton,
+    Flame,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct FractalParams {
+    pub kind: FractalKind,
+    pub center: (f64, f64),
+    pub zoom: f64,
+    pub max_iterations: usize,
+    pub julia_constant: (f64, f64),
+    pub seed: u64,
+}
+
+impl FractalParams {
+    pub fn new(kind: FractalKind) -> Self {
+        let center = if kind == FractalKind::Mandelbrot { (-0.5, 0.0) } else { (0.0, 0.0) };
+        Self { kind, center, zoom: 1.0, max_iterations: 128, julia_constant: (-0.8, 0.156), seed: 0 }
+    }
+
+    // Derives parameters from node state so the field is a reproducible function of its history:
+    // the chaos history picks the seed and drifts the center, cosmic entropy sets zoom and
+    // iteration depth, and emotional valence walks the Julia constant around the main cardioid.
+    pub fn from_node_state(kind: FractalKind, cosmic_entropy: f64, valence: f64, chaos_history: &[f64]) -> Self {
+        let seed = chaos_history.iter().fold(0xCBF2_9CE4_8422_2325u64, |hash, value| (hash ^ value.to_bits()).wrapping_mul(0x0000_0100_0000_01B3));
+        let mut rng = StdRng::seed_from_u64(seed);
+        let entropy = cosmic_entropy.abs().ln_1p();
+        let angle = std::f64::consts::PI * valence.clamp(-1.0, 1.0);
+        let cardioid = Complex64::from_polar(0.5, angle) * (1.0 - Complex64::from_polar(0.5, angle));
+        let mut params = Self::new(kind);
+        params.center.0 += rng.gen_range(-0.25..0.25);
+        params.center.1 += rng.gen_range(-0.25..0.25);
+        params.zoom = 1.0 + entropy;
+        params.max_iterations = 64 + (entropy * 32.0) as usize;
+        params.julia_constant = (cardioid.re * 1.02, cardioid.im * 1.02);
+        params.seed = seed;
+        params
+    }
+
+    // Maps a normalised position in [-1, 1]^2 onto the complex plane.
+    fn point(&self, x: f64, y: f64) -> Complex64 {
+        let scale = 1.5 / self.zoom;
+        Complex64::new(self.center.0 + x * scale, self.center.1 + y * scale)
+    }
+}
+
+#[derive(Clone)]
+pub struct FractalGenerator {
+    pub params: FractalParams,
+}
+
+impl FractalGenerator {
+    pub fn new(params: FractalParams) -> Self { Self { params } }
+
+    // Fills a `width` x `height` grid (row-major, rows along the imaginary axis) with values in [0, 1].
+    pub fn render(&self, width: usize, height: usize) -> Vec<f64> {
+        if self.params.kind == FractalKind::Flame { return self.render_flame(width, height); }
+        (0..height)
+            .flat_map(|row| (0..width).map(move |col| (row, col)))
+            .map(|(row, col)| {
+                let x = (2 * col + 1) as f64 / width as f64 - 1.0;
+                let y = (2 * row + 1) as f64 / height as f64 - 1.0;
+                self.sample(self.params.point(x, y))
+            })
+            .collect()
+    }
+
+    // Normalised smooth escape time; points that never escape score 1.
+    pub fn sample(&self, point: Complex64) -> f64 {
+        let max = self.params.max_iterations;
+        let julia = Complex64::new(self.params.julia_constant.0, self.params.julia_constant.1);
+        let (mut z, c) = match self.params.kind {
+            FractalKind::Julia => (point, julia),
+            FractalKind::Newton => return self.sample_newton(point),
+            _ => (Complex64::new(0.0, 0.0), point),
+        };
+        for iteration in 0..max {
+            if self.params.kind == FractalKind::BurningShip { z = Complex64::new(z.re.abs(), z.im.abs()); }
+            z = z * z + c;
+            let norm = z.norm_sqr();
+            if norm > 256.0 {
+                let smooth = iteration as f64 + 1.0 - norm.ln().ln() / std::f64::consts::LN_2 + 1.0;
+                return (smooth / max as f64).clamp(0.0, 1.0);
+            }
+        }
+        1.0
+    }
+
+    // Newton's method on z^3 - 1: the root reached picks a band, convergence speed shades it.
+    fn sample_newton(&self, mut z: Complex64) -> f64 {
+        let roots = [Complex64::new(1.0, 0.0), Complex64::from_polar(1.0, 2.0 * std::f64::consts::FRAC_PI_3), Complex64::from_polar(1.0, -2.0 * std::f64::consts::FRAC_PI_3)];
+        let max = self.params.max_iterations;
+        for iteration in 0..max {
+            let derivative = 3.0 * z * z;
+            if derivative.norm_sqr() < 1e-18 { break; }
+            z -= (z * z * z - 1.0) / derivative;
+            if let Some(root) = roots.iter().position(|root| (z - root).norm_sqr() < 1e-12) {
+                let speed = 1.0 - iteration as f64 / max as f64;
+                return (root as f64 + speed) / 3.0;
+            }
+        }
+        0.0
+    }
+
+    // Chaos-game flame with three affine maps and sinusoidal/spherical variations, log-density tone mapped.
+    fn render_flame(&self, width: usize, height: usize) -> Vec<f64> {
+        let mut rng = StdRng::seed_from_u64(self.params.seed);
+        let c = self.params.julia_constant;
+        let maps = [
+            [0.5, 0.0, 0.0, 0.5, -0.5 + c.0, 0.0],
+            [0.5, 0.0, 0.0, 0.5, 0.5, c.1],
+            [0.4 + c.0 * 0.2, -0.3, 0.3, 0.4, 0.0, 0.5],
+        ];
+        let mut density = vec![0.0; width * height];
+        let (mut x, mut y) = (rng.gen_range(-1.0..1.0), rng.gen_range(-1.0..1.0));
+        let samples = width * height * self.params.max_iterations.max(1) / 8;
+        for step in 0..samples + 20 {
+            let choice = rng.gen_range(0..maps.len());
+            let m = maps[choice];
+            let (ax, ay) = (m[0] * x + m[1] * y + m[4], m[2] * x + m[3] * y + m[5]);
+            (x, y) = match choice {
+                0 => (ax.sin(), ay.sin()),
+                1 => { let r2 = ax * ax + ay * ay + 1e-9; (ax / r2, ay / r2) }
+                _ => (ax, ay),
+            };
+            if step < 20 { continue; }
+            let scale = 1.5 / self.params.zoom;
+            let col = ((x - self.params.center.0) / scale + 1.0) * 0.5 * width as f64;
+            let row = ((y - self.params.center.1) / scale + 1.0) * 0.5 * height as f64;
+            if (0.0..width as f64).contains(&col) && (0.0..height as f64).contains(&row) {
+                density[row as usize * width + col as usize] += 1.0;
+            }
+        }
+        let peak = density.iter().cloned().fold(0.0, f64::max).ln_1p().max(f64::EPSILON);
+        density.iter().map(|d| d.ln_1p() / peak).collect()
+    }
+
+    // Axis 0 runs along the imaginary axis and axis 1 along the real axis; further axes repeat
+    // the same image.
+    pub fn seed_field<const D: usize>(&self, field: &mut FieldTensor<D>) {
+        self.modulate_field(field, 1.0);
+    }
+
+    // Blends the fractal into the field: `value = (1 - strength) * value + strength * fractal`.
+    pub fn modulate_field<const D: usize>(&self, field: &mut FieldTensor<D>, strength: f64) {
+        let image = self.render_for(field.shape());
+        Self::blend_image(field, &image, strength);
+    }
+
+    // The image `modulate_field` blends into a field of this shape.
+    pub fn render_for<const D: usize>(&self, shape: [usize; D]) -> Vec<f64> {
+        self.render(if D > 1 { shape[1] } else { 1 }, shape[0])
+    }
+
+    // `modulate_field` with an image already rendered by `render_for`.
+    pub fn blend_image<const D: usize>(field: &mut FieldTensor<D>, image: &[f64], strength: f64) {
+        let cols = if D > 1 { field.shape()[1] } else { 1 };
+        for index in 0..field.len() {
+            let coords = field.coords(index);
+            let pixel = image[coords[0] * cols + if D > 1 { coords[1] } else { 0 }];
+            let value = &mut field.as_mut_slice()[index];
+            *value = (1.0 - strength) * *value + strength * pixel;
+        }
+    }
+}
@@ -1,43 +1,295 @@
+use std::sync::Arc;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use rayon::prelude::*;
+use crate::quantum::amplitude_field::{AmplitudeField, MeasurementOutcome, Region};
+use crate::quantum::field_analysis::{FieldAnalyzer, FieldStatistics, LyapunovEstimator};
+use crate::quantum::field_rules::{normalized_position, FieldRule, Neighborhood, QuantumDecay, RowStencil};
+use crate::quantum::field_tensor::FieldTensor;
+use crate::quantum::fractal_generators::{FractalGenerator, FractalParams};
+use crate::quantum::tensor_shard::TensorShard;
+
+const DEFAULT_SEED: u64 = 0x4_4_1;
+const INITIAL_TICK: u64 = u64::MAX;
+
+pub type QuantumFractalTensorEngine3D = QuantumFractalTensorEngine<3>;
+
+// Every channel of a perturbed copy of the field, evolved alongside it for the Lyapunov estimate.
 #[derive(Clone)]
-pub struct QuantumFractalTensorEngine {
-    pub tensor_field: Vec<Vec<f64>>,
+struct LyapunovTwin<const D: usize> {
+    estimator: LyapunovEstimator,
+    channels: Vec<FieldTensor<D>>,
+}
+
+impl<const D: usize> LyapunovTwin<D> {
+    fn new(reference: &[&FieldTensor<D>], epsilon: f64) -> Self {
+        let estimator = LyapunovEstimator::new(epsilon);
+        let flat: Vec<f64> = reference.iter().flat_map(|field| field.as_slice().iter().copied()).collect();
+        let perturbed = estimator.perturb(&flat);
+        let channels = reference.iter().zip(perturbed.chunks(reference[0].len().max(1))).map(|(field, data)| FieldTensor::from_data(field.shape(), data.to_vec())).collect();
+        Self { estimator, channels }
+    }
+
+    // Measures the separation over every owned cell and pulls the twin back to `epsilon`.
+    fn renormalize(&mut self, reference: &[TensorShard<D>], twin: &mut [TensorShard<D>]) {
+        let separation = reference
+            .iter()
+            .zip(twin.iter())
+            .flat_map(|(r, t)| (0..r.channel_count()).flat_map(move |channel| r.owned(channel).iter().zip(t.owned(channel))))
+            .map(|(r, t)| (t - r) * (t - r))
+            .sum::<f64>()
+            .sqrt();
+        let scale = self.estimator.record(separation);
+        for (r, t) in reference.iter().zip(twin.iter_mut()) {
+            for channel in 0..r.channel_count() {
+                for (t, r) in t.owned_mut(channel).iter_mut().zip(r.owned(channel)) { *t = r + (*t - r) * scale; }
+            }
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct QuantumFractalTensorEngine<const D: usize = 2> {
+    pub tensor_field: FieldTensor<D>,
     pub cosmic_entropy: f64,
+    aux_fields: Vec<FieldTensor<D>>,
+    rule: Arc<dyn FieldRule<D>>,
+    amplitudes: Option<AmplitudeField<D>>,
+    measurement_rng: StdRng,
+    analyzer: FieldAnalyzer<D>,
+    last_statistics: Option<FieldStatistics>,
+    lyapunov: Option<LyapunovTwin<D>>,
     shard_count: usize,
-    shards: Vec<Vec<Vec<f64>>>,
+    seed: u64,
+    tick: u64,
+}
+
+impl QuantumFractalTensorEngine<2> {
+    pub fn new() -> Self { Self::with_config([200, 200], 2, DEFAULT_SEED) }
 }
 
-impl QuantumFractalTensorEngine {
-    pub fn new() -> Self {
-        let shard_size = 200;
-        let shard_count = 2;
-        let tensor_field = vec![vec![0.0; shard_size]; shard_size];
+impl<const D: usize> QuantumFractalTensorEngine<D> {
+    pub fn with_config(shape: [usize; D], shard_count: usize, seed: u64) -> Self {
+        assert!(D >= 1, "tensor field needs at least one dimension");
         Self {
-            tensor_field,
+            tensor_field: FieldTensor::zeros(shape),
             cosmic_entropy: 0.0,
-            shard_count,
-            shards: vec![tensor_field.clone(); shard_count],
+            aux_fields: Vec::new(),
+            rule: Arc::new(QuantumDecay::new()),
+            amplitudes: None,
+            measurement_rng: StdRng::seed_from_u64(seed),
+            analyzer: FieldAnalyzer::new(32, 4),
+            last_statistics: None,
+            lyapunov: None,
+            shard_count: shard_count.max(1),
+            seed,
+            tick: 0,
         }
     }
 
-    pub fn update_field(&mut self, node: &super::self_evolving_fractal_gossip_node::SelfEvolvingFractalGossipNode) {
-        use rayon::prelude::*;
+    pub fn shape(&self) -> [usize; D] { self.tensor_field.shape() }
+
+    pub fn shard_count(&self) -> usize { self.shard_count }
+
+    pub fn set_shard_count(&mut self, shard_count: usize) { self.shard_count = shard_count.max(1); }
+
+    pub fn rule_name(&self) -> &'static str { self.rule.name() }
+
+    // Hidden state channels of multi-channel rules (e.g. Gray-Scott's substrate).
+    pub fn aux_fields(&self) -> &[FieldTensor<D>] { &self.aux_fields }
+
+    // Switches the update rule and re-initialises every channel from the rule's initial state.
+    pub fn set_rule(&mut self, rule: impl FieldRule<D> + 'static) {
+        self.rule = Arc::new(rule);
+        let shape = self.shape();
+        let mut channels: Vec<FieldTensor<D>> = (0..self.rule.channels().max(1))
+            .map(|channel| {
+                let mut field = FieldTensor::zeros(shape);
+                let cells = field.len() as u64;
+                for index in 0..field.len() {
+                    let position = normalized_position(field.coords(index), shape);
+                    let noise = cell_noise(self.seed, INITIAL_TICK, channel as u64 * cells + index as u64);
+                    field.as_mut_slice()[index] = self.rule.initial_value(channel, position, noise);
+                }
+                field
+            })
+            .collect();
+        self.tensor_field = channels.remove(0);
+        self.aux_fields = channels;
+        self.restart_lyapunov();
+    }
+
+    // Starts a Benettin estimate of the dynamics' largest Lyapunov exponent: a twin of every
+    // channel, `epsilon` away, is evolved alongside and renormalised after each step. Doubles the
+    // cost of `evolve`.
+    pub fn track_lyapunov(&mut self, epsilon: f64) {
+        let channels: Vec<&FieldTensor<D>> = std::iter::once(&self.tensor_field).chain(&self.aux_fields).collect();
+        self.lyapunov = Some(LyapunovTwin::new(&channels, epsilon));
+    }
+
+    pub fn stop_tracking_lyapunov(&mut self) { self.lyapunov = None; }
+
+    // Mean log separation growth per step since tracking (re)started; `None` before the first step.
+    pub fn lyapunov_exponent(&self) -> Option<f64> { self.lyapunov.as_ref()?.estimator.exponent() }
+
+    // The field was replaced wholesale, so the twin no longer shadows it.
+    fn restart_lyapunov(&mut self) {
+        if let Some(epsilon) = self.lyapunov.as_ref().map(|twin| twin.estimator.epsilon) { self.track_lyapunov(epsilon); }
+    }
+
+    // Starts tracking complex amplitudes initialised from the current field. Each tick then
+    // rotates phases by the real field (as a potential) and mixes neighbours along one axis.
+    pub fn enable_amplitudes(&mut self) {
+        self.amplitudes = Some(AmplitudeField::from_real(&self.tensor_field));
+    }
+
+    pub fn disable_amplitudes(&mut self) { self.amplitudes = None; }
+
+    pub fn amplitudes(&self) -> Option<&AmplitudeField<D>> { self.amplitudes.as_ref() }
+
+    // Collapses the amplitude field within `region` using the engine's seeded RNG; `None` when
+    // amplitudes are disabled.
+    pub fn measure(&mut self, region: &Region<D>) -> Option<MeasurementOutcome<D>> {
+        let amplitudes = self.amplitudes.as_mut()?;
+        Some(amplitudes.measure(region, &mut self.measurement_rng))
+    }
+
+    pub fn seed_with_fractal(&mut self, params: &FractalParams) {
+        FractalGenerator::new(params.clone()).seed_field(&mut self.tensor_field);
+        self.restart_lyapunov();
+    }
+
+    pub fn modulate_with_fractal(&mut self, params: &FractalParams, strength: f64) {
+        let image = FractalGenerator::new(params.clone()).render_for(self.tensor_field.shape());
+        self.blend_fractal_image(&image, strength);
+    }
+
+    // `modulate_with_fractal` with an image from `FractalGenerator::render_for`, so the render can
+    // happen elsewhere.
+    pub fn blend_fractal_image(&mut self, image: &[f64], strength: f64) {
+        let before = self.lyapunov.as_ref().map(|_| self.tensor_field.clone());
+        FractalGenerator::blend_image(&mut self.tensor_field, image, strength);
+        // The blend is affine, so the twin's offset just shrinks by `1 - strength`.
+        if let (Some(twin), Some(before)) = (self.lyapunov.as_mut(), before) {
+            for ((t, before), after) in twin.channels[0].as_mut_slice().iter_mut().zip(before.as_slice()).zip(self.tensor_field.as_slice()) {
+                *t = after + (1.0 - strength) * (*t - before);
+            }
+        }
+    }
+
+    pub fn update_field(&mut self, node: &crate::core::self_evolving_fractal_gossip_node::SelfEvolvingFractalGossipNode) -> FieldStatistics {
         self.cosmic_entropy = crate::quantum::cosmic_entropy::CosmicEntropy::calculate(node);
-        self.shards.par_iter_mut().for_each(|shard| {
-            for _ in 0..8 {
-                *shard = self.recursive_quantum_transform(shard);
+        self.evolve(8);
+        self.observe_field()
+    }
+
+    // Analyses the current field, advancing the step-growth estimate across successive calls.
+    pub fn observe_field(&mut self) -> FieldStatistics {
+        let mut statistics = self.analyzer.observe(&self.tensor_field);
+        statistics.lyapunov_exponent = self.lyapunov_exponent();
+        self.last_statistics = Some(statistics.clone());
+        statistics
+    }
+
+    pub fn last_statistics(&self) -> Option<&FieldStatistics> { self.last_statistics.as_ref() }
+
+    // Noise is a pure function of (seed, tick, cell), so the result is identical for any shard count.
+    pub fn evolve(&mut self, iterations: usize) {
+        if self.tensor_field.is_empty() { return; }
+        let shape = self.shape();
+        let channels: Vec<&FieldTensor<D>> = std::iter::once(&self.tensor_field).chain(&self.aux_fields).collect();
+        let mut shards = TensorShard::partition(&channels, self.shard_count);
+        let mut twin_shards = self.lyapunov.as_ref().map(|twin| TensorShard::partition(&twin.channels.iter().collect::<Vec<_>>(), self.shard_count));
+        let layer_len = self.tensor_field.layer_len();
+        for _ in 0..iterations {
+            let (rule, seed, tick) = (self.rule.as_ref(), self.seed, self.tick);
+            shards.par_iter_mut().for_each(|shard| {
+                Self::recursive_quantum_transform(rule, shard, shape, seed, tick);
+                shard.swap_buffers();
+            });
+            // The twin sees the same noise, so only the perturbation tells the two apart.
+            if let (Some(twin), Some(twin_shards)) = (self.lyapunov.as_mut(), twin_shards.as_mut()) {
+                twin_shards.par_iter_mut().for_each(|shard| {
+                    Self::recursive_quantum_transform(rule, shard, shape, seed, tick);
+                    shard.swap_buffers();
+                });
+                twin.renormalize(&shards, twin_shards);
+                TensorShard::exchange_halos(twin_shards);
             }
-        });
-        self.tensor_field = self.shards[0].clone(); // Simplified combine
+            TensorShard::exchange_halos(&mut shards);
+            // Each step's amplitudes feel the field that step produced.
+            if let Some(amplitudes) = self.amplitudes.as_mut() {
+                for shard in &shards { amplitudes.phase_rotation_at(shard.layer_offset() * layer_len, shard.owned(0), 1.0); }
+                amplitudes.hadamard_mix(tick as usize % D, (tick as usize / D) % 2);
+                amplitudes.normalize();
+            }
+            self.tick += 1;
+        }
+        if let (Some(twin), Some(twin_shards)) = (self.lyapunov.as_mut(), twin_shards) { twin.channels = TensorShard::merge(&twin_shards, shape); }
+        let mut merged = TensorShard::merge(&shards, shape);
+        self.tensor_field = merged.remove(0);
+        self.aux_fields = merged;
     }
 
-    fn recursive_quantum_transform(&self, field: &[Vec<f64>]) -> Vec<Vec<f64>> {
-        let mut new_field = field.to_vec();
-        for i in 0..200 {
-            for j in 0..200 {
-                let noise = rand::random::<f64>() * 0.16 - 0.08;
-                new_field[i][j] = field[i][j] * 0.5 + noise * 0.15;
+    // Walks the shard's owned cells one last-axis row at a time, trying the rule's row kernel
+    // before falling back to per-cell updates.
+    fn recursive_quantum_transform(rule: &dyn FieldRule<D>, shard: &mut TensorShard<D>, shape: [usize; D], seed: u64, tick: u64) {
+        let layer_offset = shard.layer_offset();
+        let owned_cells = shard.owned_layers() * shard.slabs_with_halo()[0].layer_len();
+        let (current, next, scratch) = shard.step_buffers();
+        let slab = &current[0];
+        let (slab_shape, strides, layer_len) = (slab.shape(), slab.strides(), slab.layer_len());
+        let row_len = scratch.noise.len();
+        let global_start = (layer_offset * layer_len) as u64;
+        for row in 0..owned_cells / row_len.max(1) {
+            // Owned rows sit at the same offsets in the current and next slabs.
+            let start = layer_len + row * row_len;
+            let coords = slab.coords(start);
+            for (j, value) in scratch.noise.iter_mut().enumerate() {
+                *value = cell_noise(seed, tick, global_start + (start - layer_len + j) as u64);
+            }
+            let mut fast = false;
+            if D >= 2 {
+                for (k, begin) in scratch.neighbor_starts.iter_mut().enumerate() {
+                    *begin = shifted_row(start, coords, slab_shape, strides, k / 2, if k % 2 == 0 { -1 } else { 1 });
+                }
+                let stencil = RowStencil::new(current, start, &scratch.neighbor_starts, &scratch.noise);
+                fast = rule.update_row(&stencil, &mut scratch.row_out);
+            }
+            if !fast {
+                for j in 0..row_len {
+                    let index = start + j;
+                    let cell_coords = slab.coords(index);
+                    let mut global = cell_coords;
+                    global[0] = cell_coords[0] + layer_offset - 1;
+                    let cell = Neighborhood::new(current, slab_shape, strides, index, cell_coords, normalized_position(global, shape), scratch.noise[j]);
+                    rule.update(&cell, &mut scratch.cell_out);
+                    for (channel, value) in scratch.cell_out.iter().enumerate() { scratch.row_out[channel * row_len + j] = *value; }
+                }
+            }
+            for (slab, values) in next.iter_mut().zip(scratch.row_out.chunks(row_len)) {
+                slab.as_mut_slice()[start..start + row_len].copy_from_slice(values);
             }
         }
-        new_field
     }
 }
+
+// Start of the row one step along `axis` from the row starting at `start`; axis 0 steps into
+// the halo layers, every other axis wraps.
+fn shifted_row<const D: usize>(start: usize, coords: [usize; D], shape: [usize; D], strides: [usize; D], axis: usize, step: isize) -> usize {
+    if axis == 0 {
+        return if step < 0 { start - strides[0] } else { start + strides[0] };
+    }
+    let (n, c) = (shape[axis] as isize, coords[axis] as isize);
+    let moved = (c + step).rem_euclid(n) as usize;
+    start - coords[axis] * strides[axis] + moved * strides[axis]
+}
+
+// SplitMix64 over (seed, tick, cell), mapped to [0, 1).
+pub(crate) fn cell_noise(seed: u64, tick: u64, cell: u64) -> f64 {
+    let mut z = seed ^ tick.wrapping_mul(0x9E37_79B9_7F4A_7C15) ^ cell.wrapping_mul(0xD1B5_4A32_D192_ED03);
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^= z >> 31;
+    (z >> 11) as f64 / (1u64 << 53) as f64
+}
@@ -0,0 +1,139 @@
+use crate::quantum::field_tensor::FieldTensor;
+
+// Per-row working memory, sized once when the shard is built.
+#[derive(Clone)]
+pub struct RowScratch {
+    pub noise: Vec<f64>,
+    pub neighbor_starts: Vec<usize>,
+    // `noise.len()` values per channel, one channel after another.
+    pub row_out: Vec<f64>,
+    pub cell_out: Vec<f64>,
+}
+
+// A slab of every field channel along axis 0 plus one halo layer on either side, copied from
+// the neighbouring shards (every axis wraps toroidally) so stencil updates never cross shards.
+// Each channel is double-buffered: rules read `front` and write the owned layers of `back`,
+// then the buffers swap. Together with the reused `scratch`, stepping allocates nothing.
+#[derive(Clone)]
+pub struct TensorShard<const D: usize> {
+    layer_offset: usize,
+    front: Vec<FieldTensor<D>>,
+    back: Vec<FieldTensor<D>>,
+    scratch: RowScratch,
+}
+
+impl<const D: usize> TensorShard<D> {
+    pub fn partition(channels: &[&FieldTensor<D>], shard_count: usize) -> Vec<TensorShard<D>> {
+        let layers = channels[0].shape()[0];
+        let layer_len = channels[0].layer_len();
+        let shard_count = shard_count.clamp(1, layers.max(1));
+        let (base, remainder) = (layers / shard_count, layers % shard_count);
+        let mut layer_offset = 0;
+        let mut shards: Vec<TensorShard<D>> = (0..shard_count)
+            .map(|k| {
+                let owned = base + usize::from(k < remainder);
+                let mut shape = channels[0].shape();
+                shape[0] = owned + 2;
+                let front: Vec<FieldTensor<D>> = channels
+                    .iter()
+                    .map(|field| {
+                        let mut slab = FieldTensor::zeros(shape);
+                        slab.as_mut_slice()[layer_len..(owned + 1) * layer_len]
+                            .copy_from_slice(&field.as_slice()[layer_offset * layer_len..(layer_offset + owned) * layer_len]);
+                        slab
+                    })
+                    .collect();
+                let back = front.clone();
+                // A 1D slab is a single run of its owned cells.
+                let row_len = if D == 1 { owned } else { shape[D - 1] };
+                let scratch = RowScratch {
+                    noise: vec![0.0; row_len],
+                    neighbor_starts: vec![0; 2 * (D - 1)],
+                    row_out: vec![0.0; channels.len() * row_len],
+                    cell_out: vec![0.0; channels.len()],
+                };
+                let shard = TensorShard { layer_offset, front, back, scratch };
+                layer_offset += owned;
+                shard
+            })
+            .collect();
+        Self::exchange_halos(&mut shards);
+        shards
+    }
+
+    pub fn layer_offset(&self) -> usize { self.layer_offset }
+
+    pub fn owned_layers(&self) -> usize { self.front[0].shape()[0] - 2 }
+
+    pub fn channel_count(&self) -> usize { self.front.len() }
+
+    // Current per-channel slabs including halos: layer 0 sits just before `layer_offset`.
+    pub fn slabs_with_halo(&self) -> &[FieldTensor<D>] { &self.front }
+
+    // The current slabs, the next ones (same layout, only owned layers are written) and the
+    // shard's scratch space.
+    pub fn step_buffers(&mut self) -> (&[FieldTensor<D>], &mut [FieldTensor<D>], &mut RowScratch) { (&self.front, &mut self.back, &mut self.scratch) }
+
+    pub fn swap_buffers(&mut self) { std::mem::swap(&mut self.front, &mut self.back); }
+
+    pub fn owned(&self, channel: usize) -> &[f64] {
+        let layer_len = self.front[channel].layer_len();
+        &self.front[channel].as_slice()[layer_len..layer_len * (self.owned_layers() + 1)]
+    }
+
+    pub fn owned_mut(&mut self, channel: usize) -> &mut [f64] {
+        let (layer_len, owned_layers) = (self.front[channel].layer_len(), self.owned_layers());
+        &mut self.front[channel].as_mut_slice()[layer_len..layer_len * (owned_layers + 1)]
+    }
+
+    pub fn exchange_halos(shards: &mut [TensorShard<D>]) {
+        let count = shards.len();
+        for k in 0..count {
+            let next = (k + 1) % count;
+            if next == k {
+                shards[k].wrap_own_halos();
+                continue;
+            }
+            let (upper, lower) = pair_mut(shards, k, next);
+            for channel in 0..upper.channel_count() {
+                let layer_len = upper.front[channel].layer_len();
+                let upper_last = upper.owned_layers() * layer_len;
+                let upper_halo = upper_last + layer_len;
+                lower.front[channel].as_mut_slice()[..layer_len].copy_from_slice(&upper.front[channel].as_slice()[upper_last..upper_halo]);
+                upper.front[channel].as_mut_slice()[upper_halo..].copy_from_slice(&lower.front[channel].as_slice()[layer_len..2 * layer_len]);
+            }
+        }
+    }
+
+    fn wrap_own_halos(&mut self) {
+        let last = self.owned_layers();
+        for slab in &mut self.front {
+            let layer_len = slab.layer_len();
+            let data = slab.as_mut_slice();
+            data.copy_within(last * layer_len..(last + 1) * layer_len, 0);
+            data.copy_within(layer_len..2 * layer_len, (last + 1) * layer_len);
+        }
+    }
+
+    pub fn merge(shards: &[TensorShard<D>], shape: [usize; D]) -> Vec<FieldTensor<D>> {
+        let mut ordered: Vec<&TensorShard<D>> = shards.iter().collect();
+        ordered.sort_by_key(|shard| shard.layer_offset);
+        (0..ordered[0].channel_count())
+            .map(|channel| {
+                let mut data = Vec::with_capacity(shape.iter().product());
+                for shard in &ordered { data.extend_from_slice(shard.owned(channel)); }
+                FieldTensor::from_data(shape, data)
+            })
+            .collect()
+    }
+}
+
+fn pair_mut<T>(items: &mut [T], a: usize, b: usize) -> (&mut T, &mut T) {
+    if a < b {
+        let (head, tail) = items.split_at_mut(b);
+        (&mut head[a], &mut tail[0])
+    } else {
+        let (head, tail) = items.split_at_mut(a);
+        (&mut tail[0], &mut head[b])
+    }
+}
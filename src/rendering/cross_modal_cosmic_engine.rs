@@ -20,7 +20,7 @@ impl CrossModalCosmicEngine {
         }
     }
 
-    pub async fn render_live_fractal(&self, tensor_engine: &QuantumFractalTensorEngine) -> (image::DynamicImage, image::DynamicImage, image::DynamicImage) {
+    pub async fn render_live_fractal<const D: usize>(&self, tensor_engine: &QuantumFractalTensorEngine<D>) -> (image::DynamicImage, image::DynamicImage, image::DynamicImage) {
         self.last_entropy = tensor_engine.cosmic_entropy;
         let delay = Duration::from_secs_f64((1.0f64).max(self.last_entropy / 20000.0));
         sleep(delay).await;
@@ -28,7 +28,7 @@ impl CrossModalCosmicEngine {
     }
 
     pub async fn render_live_soundscape(&self, emotional_model: &EmotionalStateModel) {
-        let coherence = *emotional_model.emotional_dimensions.get("cosmic_resonance").unwrap_or(&0.0);
+        let coherence = emotional_model.get_cosmic_resonance();
         self.sonification.sonify(coherence);
     }
 }
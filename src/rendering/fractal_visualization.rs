@@ -1,4 +1,5 @@
 use image::{DynamicImage, ImageBuffer, Rgba};
+use crate::quantum::field_tensor::{FieldTensor, Plane};
 
 #[derive(Clone)]
 pub struct FractalVisualization;
@@ -6,34 +7,27 @@ pub struct FractalVisualization;
 impl FractalVisualization {
     pub fn new() -> Self { Self }
 
-    pub fn render_3d(&self, field: &[Vec<f64>]) -> (DynamicImage, DynamicImage, DynamicImage) {
+    // XY, XZ and YZ cross-sections through the field's center. A 2D field is rendered as a
+    // one-voxel-deep volume, so its XZ and YZ planes are single rows stretched to fit.
+    pub fn render_3d<const D: usize>(&self, field: &FieldTensor<D>) -> (DynamicImage, DynamicImage, DynamicImage) {
+        let center = field.center_coords();
         let size = if field.len() > 400_000 { 200 } else { 400 };
-        let mut xy_img = ImageBuffer::new(size as u32, size as u32);
-        let mut xz_img = ImageBuffer::new(size as u32, size as u32);
-        let mut yz_img = ImageBuffer::new(size as u32, size as u32);
+        (
+            self.render_plane(&field.plane(0, 1, center), size, [255, 255]),
+            self.render_plane(&field.plane(0, 2, center), size, [255, 204]),
+            self.render_plane(&field.plane(1, 2, center), size, [204, 255]),
+        )
+    }
 
-        for x in 0..size {
-            for y in 0..size {
-                let value = field[x * field.len() / size][y * field.len() / size];
-                let hue = (value * 255.0) as u8;
-                xy_img.put_pixel(x as u32, y as u32, Rgba([hue, 255, 255, 255]));
-            }
-        }
-        for x in 0..size {
-            for z in 0..size {
-                let value = field[x * field.len() / size][z * field.len() / size];
+    fn render_plane(&self, plane: &Plane, size: usize, tint: [u8; 2]) -> DynamicImage {
+        let mut img = ImageBuffer::new(size as u32, size as u32);
+        for a in 0..size {
+            for b in 0..size {
+                let value = plane.get(a * plane.width / size, b * plane.height / size);
                 let hue = (value * 255.0) as u8;
-                xz_img.put_pixel(x as u32, z as u32, Rgba([hue, 255, 204, 255]));
+                img.put_pixel(a as u32, b as u32, Rgba([hue, tint[0], tint[1], 255]));
             }
         }
-        for y in 0..size {
-            for z in 0..size {
-                let value = field[y * field.len() / size][z * field.len() / size];
-                let hue = (value * 255.0) as u8;
-                yz_img.put_pixel(y as u32, z as u32, Rgba([hue, 204, 255, 255]));
-            }
-        }
-
-        (DynamicImage::ImageRgba8(xy_img), DynamicImage::ImageRgba8(xz_img), DynamicImage::ImageRgba8(yz_img))
+        DynamicImage::ImageRgba8(img)
     }
 }
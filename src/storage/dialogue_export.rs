@@ -0,0 +1,70 @@
+use crate::core::dialogue_frame::DialogueFrame;
+use crate::storage::state_manager::StateManager;
+use crate::utils::csv::quote;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ExportFormat {
+    Json,
+    Csv,
+}
+
+impl ExportFormat {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "json" => Some(ExportFormat::Json),
+            "csv" => Some(ExportFormat::Csv),
+            _ => None,
+        }
+    }
+}
+
+// Dumps a node's dialogue log: the `limit` best matches for `search` when given, otherwise every
+// logged frame, optionally restricted to one session.
+pub fn export_dialogue(manager: &StateManager, session_id: Option<&str>, search: Option<&str>, limit: usize, format: ExportFormat) -> String {
+    let frames = match search {
+        Some(query) => manager.search_dialogue(query, session_id, limit),
+        None => manager.load_dialogue(session_id),
+    };
+    match format {
+        ExportFormat::Json => export_json(&frames),
+        ExportFormat::Csv => export_csv(&frames),
+    }
+}
+
+// `[{"timestamp": "...", "session_id": "...", "speaker": "user", "depth": 0, "content": "...", "emotions": {"pleasure": 0.1, ...}}, ...]`
+pub fn export_json(frames: &[DialogueFrame]) -> String {
+    let records: Vec<serde_json::Value> = frames
+        .iter()
+        .map(|frame| {
+            let emotions: serde_json::Map<String, serde_json::Value> = frame.emotions.iter().map(|(dimension, value)| (dimension.label(), serde_json::json!(value))).collect();
+            serde_json::json!({
+                "timestamp": frame.timestamp.to_rfc3339(),
+                "session_id": frame.session_id,
+                "speaker": frame.speaker.name(),
+                "depth": frame.depth,
+                "content": frame.content,
+                "emotions": emotions,
+            })
+        })
+        .collect();
+    serde_json::to_string(&records).unwrap()
+}
+
+// `timestamp,session_id,speaker,depth,content,emotions` with emotions as `label=value;...`.
+// Text fields are quoted with embedded quotes doubled.
+pub fn export_csv(frames: &[DialogueFrame]) -> String {
+    let mut csv = "timestamp,session_id,speaker,depth,content,emotions\n".to_string();
+    for frame in frames {
+        let emotions: Vec<String> = frame.emotions.iter().map(|(dimension, value)| format!("{}={}", dimension.label(), value)).collect();
+        csv += &format!(
+            "{},{},{},{},{},{}\n",
+            frame.timestamp.to_rfc3339(),
+            quote(&frame.session_id),
+            frame.speaker.name(),
+            frame.depth,
+            quote(&frame.content),
+            quote(&emotions.join(";"))
+        );
+    }
+    csv
+}
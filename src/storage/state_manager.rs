@@ -1,4 +1,10 @@
+use std::collections::BTreeMap;
+use chrono::{TimeZone, Utc};
 use rusqlite::{Connection, params};
+use crate::core::chaos_time_series::Rollup;
+use crate::core::dialogue_frame::{DialogueFrame, Speaker};
+use crate::core::emotional_state_model::Dimension;
+use crate::core::text_pipeline::{normalize, tokenize};
 use crate::network::cosmic_gossip_protocol::State;
 
 #[derive(Clone)]
@@ -10,10 +16,91 @@ impl StateManager {
     pub fn new(peer_id: String) -> Self {
         let conn = Connection::open(format!("backup_{}.sqlite", peer_id)).unwrap();
         conn.execute("CREATE TABLE IF NOT EXISTS states (cid TEXT PRIMARY KEY, encrypted TEXT)", []).unwrap();
+        conn.execute("CREATE TABLE IF NOT EXISTS chaos_rollups (resolution TEXT, start INTEGER, count INTEGER, min REAL, max REAL, mean REAL, PRIMARY KEY (resolution, start))", []).unwrap();
+        conn.execute("CREATE TABLE IF NOT EXISTS dialogue (id INTEGER PRIMARY KEY AUTOINCREMENT, session_id TEXT, speaker TEXT, content TEXT, depth INTEGER, timestamp INTEGER, emotions TEXT)", []).unwrap();
+        conn.execute("CREATE INDEX IF NOT EXISTS dialogue_session ON dialogue (session_id, id)", []).unwrap();
+        // External-content FTS5 index over `dialogue.content`, kept in step by `save_dialogue`.
+        conn.execute("CREATE VIRTUAL TABLE IF NOT EXISTS dialogue_fts USING fts5(content, content='dialogue', content_rowid='id')", []).unwrap();
         Self { conn }
     }
 
     pub fn save_state(&self, state: &State) {
         self.conn.execute("INSERT OR REPLACE INTO states (cid, encrypted) VALUES (?1, ?2)", params![state.cid, state.encrypted]).unwrap();
     }
+
+    pub fn save_rollup(&self, resolution: &str, rollup: &Rollup) {
+        self.conn.execute(
+            "INSERT OR REPLACE INTO chaos_rollups (resolution, start, count, min, max, mean) VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            params![resolution, rollup.start.timestamp_millis(), rollup.count as i64, rollup.min, rollup.max, rollup.mean],
+        ).unwrap();
+    }
+
+    // Oldest first.
+    pub fn load_rollups(&self, resolution: &str) -> Vec<Rollup> {
+        let mut statement = self.conn.prepare("SELECT start, count, min, max, mean FROM chaos_rollups WHERE resolution = ?1 ORDER BY start").unwrap();
+        statement
+            .query_map(params![resolution], |row| {
+                Ok(Rollup {
+                    start: Utc.timestamp_millis_opt(row.get(0)?).unwrap(),
+                    count: row.get::<_, i64>(1)? as usize,
+                    min: row.get(2)?,
+                    max: row.get(3)?,
+                    mean: row.get(4)?,
+                })
+            })
+            .unwrap()
+            .map(Result::unwrap)
+            .collect()
+    }
+
+    pub fn save_dialogue(&self, frame: &DialogueFrame) {
+        // Dimension keys aren't all strings, so emotions are stored as a list of pairs.
+        let emotions = serde_json::to_string(&frame.emotions.iter().collect::<Vec<_>>()).unwrap();
+        self.conn.execute(
+            "INSERT INTO dialogue (session_id, speaker, content, depth, timestamp, emotions) VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            params![frame.session_id, frame.speaker.name(), frame.content, frame.depth as i64, frame.timestamp.timestamp_millis(), emotions],
+        ).unwrap();
+        self.conn.execute("INSERT INTO dialogue_fts (rowid, content) VALUES (?1, ?2)", params![self.conn.last_insert_rowid(), frame.content]).unwrap();
+    }
+
+    // Oldest first; every session when `session_id` is None.
+    pub fn load_dialogue(&self, session_id: Option<&str>) -> Vec<DialogueFrame> {
+        let mut statement = self.conn.prepare(&format!("SELECT {} FROM dialogue WHERE ?1 IS NULL OR session_id = ?1 ORDER BY id", DIALOGUE_COLUMNS)).unwrap();
+        statement.query_map(params![session_id], dialogue_frame).unwrap().map(Result::unwrap).collect()
+    }
+
+    // The newest `per_session` frames of every session, oldest first.
+    pub fn load_recent_dialogue(&self, per_session: usize) -> Vec<DialogueFrame> {
+        let mut statement = self.conn.prepare(&format!(
+            "SELECT {} FROM (SELECT *, ROW_NUMBER() OVER (PARTITION BY session_id ORDER BY id DESC) AS recency FROM dialogue) AS dialogue WHERE recency <= ?1 ORDER BY id",
+            DIALOGUE_COLUMNS
+        )).unwrap();
+        statement.query_map(params![per_session as i64], dialogue_frame).unwrap().map(Result::unwrap).collect()
+    }
+
+    // Full-text search, best match first. Query words are quoted so user text can't inject FTS5
+    // syntax; a frame must contain all of them.
+    pub fn search_dialogue(&self, query: &str, session_id: Option<&str>, limit: usize) -> Vec<DialogueFrame> {
+        let terms: Vec<String> = tokenize(&normalize(query)).iter().map(|token| format!("\"{}\"", token)).collect();
+        if terms.is_empty() { return Vec::new(); }
+        let mut statement = self.conn.prepare(&format!(
+            "SELECT {} FROM dialogue_fts JOIN dialogue ON dialogue.id = dialogue_fts.rowid WHERE dialogue_fts MATCH ?1 AND (?2 IS NULL OR session_id = ?2) ORDER BY dialogue_fts.rank LIMIT ?3",
+            DIALOGUE_COLUMNS
+        )).unwrap();
+        statement.query_map(params![terms.join(" "), session_id, limit as i64], dialogue_frame).unwrap().map(Result::unwrap).collect()
+    }
+}
+
+const DIALOGUE_COLUMNS: &str = "dialogue.session_id, dialogue.speaker, dialogue.content, dialogue.depth, dialogue.timestamp, dialogue.emotions";
+
+fn dialogue_frame(row: &rusqlite::Row) -> rusqlite::Result<DialogueFrame> {
+    let emotions: Vec<(Dimension, f64)> = serde_json::from_str(&row.get::<_, String>(5)?).unwrap();
+    Ok(DialogueFrame {
+        session_id: row.get(0)?,
+        speaker: Speaker::from_name(&row.get::<_, String>(1)?).unwrap(),
+        content: row.get(2)?,
+        depth: row.get::<_, i64>(3)? as usize,
+        timestamp: Utc.timestamp_millis_opt(row.get(4)?).unwrap(),
+        emotions: emotions.into_iter().collect::<BTreeMap<_, _>>(),
+    })
 }
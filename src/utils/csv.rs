@@ -0,0 +1,2 @@
+// A CSV text field: wrapped in quotes with embedded quotes doubled (RFC 4180).
+pub fn quote(field: &str) -> String { format!("\"{}\"", field.replace('"', "\"\"")) }
@@ -0,0 +1,83 @@
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use crate::quantum::amplitude_field::{AmplitudeField, MeasurementOutcome, Region};
+use crate::quantum::quantum_fractal_tensor_engine::QuantumFractalTensorEngine;
+
+#[test]
+fn test_unitary_steps_preserve_norm() {
+    let mut engine = QuantumFractalTensorEngine::with_config([16, 12], 2, 3);
+    engine.enable_amplitudes();
+    engine.evolve(40);
+    let amplitudes = engine.amplitudes().unwrap();
+    assert!((amplitudes.total_probability() - 1.0).abs() < 1e-12);
+
+    let mut state = amplitudes.clone();
+    state.hadamard_mix(1, 1);
+    state.hadamard_mix(1, 1);
+    for (a, b) in state.amplitudes().iter().zip(amplitudes.amplitudes()) {
+        assert!((a - b).norm() < 1e-12);
+    }
+}
+
+#[test]
+fn test_measurement_statistics_and_collapse() {
+    let mut field = crate::quantum::field_tensor::FieldTensor::zeros([1, 2]);
+    field[[0, 0]] = 0.2;
+    field[[0, 1]] = 0.8;
+    let prepared = AmplitudeField::from_real(&field);
+    let mut rng = StdRng::seed_from_u64(9);
+    let trials = 4000;
+    let mut second = 0;
+    for _ in 0..trials {
+        let mut state = prepared.clone();
+        match state.measure(&Region::whole([1, 2]), &mut rng) {
+            MeasurementOutcome::Found { coords, .. } => {
+                if coords == [0, 1] { second += 1; }
+                assert_eq!(state.probabilities()[coords], 1.0);
+            }
+            MeasurementOutcome::NotFound { .. } => panic!("whole-lattice measurement must find the particle"),
+        }
+    }
+    assert!((second as f64 / trials as f64 - 0.8).abs() < 0.03);
+
+    let mut state = prepared.clone();
+    let mut rng = StdRng::seed_from_u64(1);
+    let outcomes: Vec<_> = (0..20).map(|_| prepared.clone().measure(&Region::new([0, 0], [1, 1]), &mut rng)).collect();
+    assert!(outcomes.iter().any(|o| matches!(o, MeasurementOutcome::NotFound { .. })));
+    match state.measure(&Region::new([0, 0], [1, 1]), &mut StdRng::seed_from_u64(2)) {
+        MeasurementOutcome::NotFound { region_probability } => {
+            assert!((region_probability - 0.2).abs() < 1e-12);
+            assert!((state.probabilities()[[0, 1]] - 1.0).abs() < 1e-12);
+        }
+        found => panic!("expected the particle outside the region, got {:?}", found),
+    }
+
+    let mut empty = AmplitudeField::uniform([0, 3]);
+    assert_eq!(empty.measure(&Region::whole([0, 3]), &mut rng), MeasurementOutcome::NotFound { region_probability: 0.0 });
+}
+
+#[test]
+fn test_each_step_rotates_by_its_own_field() {
+    let mut stepped = QuantumFractalTensorEngine::with_config([12, 10], 3, 5);
+    let mut batched = stepped.clone();
+    stepped.enable_amplitudes();
+    batched.enable_amplitudes();
+    for _ in 0..4 { stepped.evolve(1); }
+    batched.evolve(4);
+    assert_eq!(stepped.tensor_field, batched.tensor_field);
+    for (a, b) in stepped.amplitudes().unwrap().amplitudes().iter().zip(batched.amplitudes().unwrap().amplitudes()) {
+        assert!((a - b).norm() < 1e-12);
+    }
+}
+
+#[test]
+fn test_engine_measurement_is_seeded() {
+    let run = || {
+        let mut engine = QuantumFractalTensorEngine::with_config([8, 8], 2, 42);
+        engine.enable_amplitudes();
+        engine.evolve(10);
+        (0..3).map(|_| engine.measure(&Region::new([0, 0], [4, 8]))).collect::<Vec<_>>()
+    };
+    assert_eq!(run(), run());
+    assert!(QuantumFractalTensorEngine::new().measure(&Region::whole([200, 200])).is_none());
+}
@@ -0,0 +1,56 @@
+use chrono::{Duration, TimeZone, Utc};
+use crate::core::chaos_time_series::{ChaosTimeSeries, Resolution};
+use crate::storage::state_manager::StateManager;
+
+#[test]
+fn test_rollups_and_range_queries() {
+    let start = Utc.with_ymd_and_hms(2024, 3, 1, 10, 0, 0).unwrap();
+    let mut series = ChaosTimeSeries::new(50, 200, 10);
+    // One sample every 10s for 2.5 hours: values count up within each minute.
+    for i in 0..900 {
+        series.record_at(start + Duration::seconds(10 * i), vec![(i % 6) as f64, 1.0]);
+    }
+    assert_eq!(series.len(), 50);
+    assert_eq!(series.latest().unwrap().total(), 899.0 % 6.0 + 1.0);
+
+    let minutes = series.rollups(Resolution::Minute, start, start + Duration::minutes(3));
+    assert_eq!(minutes.len(), 3);
+    for (k, minute) in minutes.iter().enumerate() {
+        assert_eq!(minute.start, start + Duration::minutes(k as i64));
+        assert_eq!((minute.count, minute.min, minute.max, minute.mean), (6, 1.0, 6.0, 3.5));
+    }
+    let hours = series.rollups(Resolution::Hour, start, start + Duration::days(1));
+    assert_eq!(hours.iter().map(|h| h.count).collect::<Vec<_>>(), vec![360, 360, 180]);
+
+    let recent = start + Duration::seconds(8900);
+    assert_eq!(series.range(recent, recent + Duration::seconds(30)).count(), 3);
+    assert_eq!(series.rollups(Resolution::Raw, recent, recent + Duration::seconds(30)).len(), 3);
+    // Raw samples from the first hour were evicted; only the rollups remain.
+    assert_eq!(series.range(start, start + Duration::hours(1)).count(), 0);
+}
+
+#[test]
+fn test_rollups_persist_through_state_manager() {
+    let peer_id = format!("chaos_series_test_{}", std::process::id());
+    let start = Utc.with_ymd_and_hms(2024, 3, 1, 0, 0, 0).unwrap();
+    let mut series = ChaosTimeSeries::new(10, 100, 10).with_persistence(StateManager::new(peer_id.clone()));
+    for i in 0..5 {
+        series.record_at(start + Duration::seconds(30 * i), vec![i as f64]);
+    }
+    // Restart in the middle of minute 2 (and of hour 0), then keep recording into both.
+    let mut restored = ChaosTimeSeries::new(10, 100, 10).with_persistence(StateManager::new(peer_id.clone()));
+    assert!(restored.is_empty());
+    let minutes = restored.rollups(Resolution::Minute, start, start + Duration::hours(1));
+    assert_eq!(minutes.len(), 3);
+    assert_eq!((minutes[1].count, minutes[1].min, minutes[1].max, minutes[1].mean), (2, 2.0, 3.0, 2.5));
+    assert_eq!((minutes[2].start, minutes[2].count), (start + Duration::minutes(2), 1));
+    restored.record_at(start + Duration::seconds(150), vec![5.0]);
+
+    let reopened = ChaosTimeSeries::new(10, 100, 10).with_persistence(StateManager::new(peer_id.clone()));
+    std::fs::remove_file(format!("backup_{}.sqlite", peer_id)).unwrap();
+    let minutes = reopened.rollups(Resolution::Minute, start, start + Duration::hours(1));
+    assert_eq!(minutes.len(), 3);
+    assert_eq!((minutes[2].count, minutes[2].min, minutes[2].max, minutes[2].mean), (2, 4.0, 5.0, 4.5));
+    let hours = reopened.rollups(Resolution::Hour, start, start + Duration::days(1));
+    assert_eq!((hours.len(), hours[0].count, hours[0].mean), (1, 6, 2.5));
+}
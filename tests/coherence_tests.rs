@@ -0,0 +1,60 @@
+use std::time::Duration;
+use crate::core::coherence::{refine, CoherenceScorer, RefinementBudget, StopReason};
+use crate::core::text_pipeline::analyze;
+
+fn budget(max_depth: usize) -> RefinementBudget { RefinementBudget { max_depth, max_duration: Duration::from_secs(5), target: 0.95 } }
+
+#[test]
+fn test_scorer_components() {
+    let scorer = CoherenceScorer::new();
+    let analysis = analyze("Describe the Lorenz attractor");
+    let grounded = scorer.score(&analysis, "The Lorenz attractor is a butterfly-shaped set that trajectories never leave.");
+    assert_eq!((grounded.keyword_overlap, grounded.length_fit), (1.0, 1.0));
+    assert!(grounded.total > 0.95);
+    let repetitive = scorer.score(&analysis, "lorenz lorenz lorenz lorenz lorenz lorenz lorenz lorenz attractor");
+    assert!(repetitive.repetition > 0.7 && repetitive.total < grounded.total);
+    let terse = scorer.score(&analysis, "Lorenz.");
+    assert_eq!((terse.keyword_overlap, terse.length_fit), (0.5, 1.0 / 8.0));
+}
+
+#[test]
+fn test_refinement_improves_and_traces_attempts() {
+    let analysis = analyze("Why does entropy rise in the Julia field?");
+    let initial = "Elias v4.4.1 reflects. Elias v4.4.1 reflects.".to_string();
+    let grounding = ["Do you remember the Julia field?", "The Julia field drifts. Entropy rises as the field loses structure.", "Peers come and go."];
+    let trace = refine(&CoherenceScorer::new(), &analysis, initial.clone(), &grounding, &budget(10));
+    assert_eq!(trace.attempts[0].candidate, initial);
+    assert_eq!(trace.attempts[0].strategy, "initial");
+    assert!(trace.best_attempt().score.total > trace.attempts[0].score.total);
+    assert!(trace.best_response().starts_with("Elias v4.4.1 reflects."));
+    // Grounded with retrieved statements, not a list of the keywords.
+    assert_eq!(trace.best_response(), "Elias v4.4.1 reflects. The Julia field drifts. Entropy rises as the field loses structure.");
+    assert!(!trace.attempts.iter().any(|attempt| attempt.candidate.contains("connects to")));
+    assert!(trace.attempts.iter().any(|attempt| attempt.strategy == "deduplicate"));
+    assert!(matches!(trace.stop_reason, StopReason::TargetReached | StopReason::Converged));
+    // Best-so-far never regresses across depths.
+    let mut best_by_depth = vec![0.0f64; trace.attempts.last().unwrap().depth + 1];
+    for attempt in &trace.attempts { best_by_depth[attempt.depth] = best_by_depth[attempt.depth].max(attempt.score.total); }
+    assert!(best_by_depth.windows(2).all(|pair| pair[1] >= pair[0]));
+}
+
+#[test]
+fn test_budget_limits() {
+    let analysis = analyze("Tell me about gossip protocols and peers");
+    let long = "Peers talk. ".repeat(40);
+    let shallow = refine(&CoherenceScorer::new(), &analysis, long.clone(), &[], &budget(0));
+    assert_eq!((shallow.attempts.len(), shallow.stop_reason), (1, StopReason::DepthExhausted));
+    let timed = refine(&CoherenceScorer::new(), &analysis, long.clone(), &[], &RefinementBudget { max_duration: Duration::ZERO, ..budget(10) });
+    assert_eq!(timed.stop_reason, StopReason::TimeExhausted);
+    let trimmed = refine(&CoherenceScorer::new(), &analysis, long, &[], &budget(10));
+    assert!(trimmed.best_response().split_whitespace().count() <= 60);
+}
+
+#[test]
+fn test_keywords_nothing_retrieved_mentions_are_not_added() {
+    let analysis = analyze("Why does entropy rise in the Julia field?");
+    let initial = "Elias v4.4.1 reflects on what the network has been doing lately.".to_string();
+    let trace = refine(&CoherenceScorer::new(), &analysis, initial.clone(), &["Peers come and go."], &budget(10));
+    assert!(trace.attempts.iter().all(|attempt| attempt.strategy != "ground_keywords"));
+    assert_eq!(trace.best_response(), initial);
+}
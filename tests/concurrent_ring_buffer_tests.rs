@@ -0,0 +1,32 @@
+use std::sync::Arc;
+use crate::core::concurrent_ring_buffer::ConcurrentRingBuffer;
+
+#[test]
+fn test_concurrent_appends_keep_newest() {
+    let buffer = Arc::new(ConcurrentRingBuffer::new(64));
+    std::thread::scope(|scope| {
+        for t in 0..8 {
+            let buffer = buffer.clone();
+            scope.spawn(move || {
+                for i in 0..1000 { buffer.append((t, i)); }
+            });
+        }
+    });
+    assert_eq!(buffer.total_appended(), 8000);
+    let snapshot = buffer.snapshot();
+    assert_eq!(snapshot.len(), 64);
+    for t in 0..8 {
+        let mine: Vec<_> = snapshot.iter().filter(|item| item.0 == t).map(|item| item.1).collect();
+        assert!(mine.windows(2).all(|w| w[0] < w[1]));
+    }
+}
+
+#[test]
+fn test_snapshot_order() {
+    let buffer = ConcurrentRingBuffer::new(3);
+    for i in 0..5 { buffer.append(i); }
+    assert_eq!(buffer.snapshot_cloned(), vec![2, 3, 4]);
+    assert_eq!(*buffer.get_latest().unwrap(), 4);
+    assert_eq!(buffer.get_latest().unwrap().sequence(), 4);
+    assert_eq!(buffer.len(), 3);
+}
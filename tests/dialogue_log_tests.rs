@@ -0,0 +1,53 @@
+use std::collections::BTreeMap;
+use chrono::{TimeZone, Utc};
+use crate::core::dialogue_frame::{DialogueFrame, Speaker};
+use crate::core::dialogue_sessions::DialogueSessions;
+use crate::core::emotional_state_model::Dimension;
+use crate::storage::dialogue_export::{export_csv, export_dialogue, export_json, ExportFormat};
+use crate::storage::state_manager::StateManager;
+
+fn frame(session_id: &str, speaker: Speaker, content: &str) -> DialogueFrame {
+    let emotions = BTreeMap::from([(Dimension::Pleasure, 0.25), (Dimension::Custom("awe".to_string()), 0.5)]);
+    DialogueFrame { timestamp: Utc.with_ymd_and_hms(2025, 3, 25, 12, 0, 0).unwrap(), ..DialogueFrame::new(session_id, speaker, content, 2).with_emotions(emotions) }
+}
+
+#[test]
+fn test_dialogue_survives_restart_and_is_searchable() {
+    let peer_id = format!("dialogue_log_test_{}", std::process::id());
+    let sessions = DialogueSessions::new(2).with_persistence(StateManager::new(peer_id.clone()));
+    sessions.record(frame("alice", Speaker::User, "Why is the Lorenz attractor shaped like a butterfly?"));
+    sessions.record(frame("alice", Speaker::Elias, "The butterfly has two lobes."));
+    sessions.record(frame("alice", Speaker::User, "And the \"third\" lobe?"));
+    sessions.record(frame("bob", Speaker::User, "Peers keep dropping, like a butterfly flapping away"));
+
+    let restored = DialogueSessions::new(2).with_persistence(StateManager::new(peer_id.clone()));
+    let manager = restored.persistence().unwrap();
+    assert_eq!(restored.history("alice"), vec![frame("alice", Speaker::Elias, "The butterfly has two lobes."), frame("alice", Speaker::User, "And the \"third\" lobe?")]);
+    assert_eq!(manager.load_dialogue(None).len(), 4);
+    assert_eq!(manager.load_dialogue(Some("alice"))[0], frame("alice", Speaker::User, "Why is the Lorenz attractor shaped like a butterfly?"));
+
+    let hits: Vec<String> = manager.search_dialogue("Butterfly", None, 10).into_iter().map(|frame| frame.session_id).collect();
+    assert_eq!(hits.len(), 3);
+    assert_eq!(manager.search_dialogue("butterfly lobes", None, 10)[0].content, "The butterfly has two lobes.");
+    assert_eq!(manager.search_dialogue("butterfly", Some("bob"), 10).len(), 1);
+    assert!(manager.search_dialogue("\" OR NEAR(", None, 10).is_empty());
+    let newest: Vec<String> = manager.load_recent_dialogue(1).into_iter().map(|frame| frame.content).collect();
+    assert_eq!(newest, vec!["And the \"third\" lobe?", "Peers keep dropping, like a butterfly flapping away"]);
+    assert_eq!(export_dialogue(manager, Some("alice"), Some("butterfly"), 10, ExportFormat::Csv).lines().count(), 3);
+    let exported: serde_json::Value = serde_json::from_str(&export_dialogue(manager, Some("bob"), None, 10, ExportFormat::from_name("json").unwrap())).unwrap();
+    assert_eq!(exported[0]["session_id"], "bob");
+    std::fs::remove_file(format!("backup_{}.sqlite", peer_id)).unwrap();
+}
+
+#[test]
+fn test_exports() {
+    let frames = vec![frame("alice", Speaker::User, "Say \"hi\", Elias"), frame("alice", Speaker::Elias, "Hi.")];
+    let json: serde_json::Value = serde_json::from_str(&export_json(&frames)).unwrap();
+    assert_eq!(json[0]["content"], "Say \"hi\", Elias");
+    assert_eq!(json[1]["speaker"], "elias");
+    assert_eq!(json[1]["emotions"]["awe"], 0.5);
+    let csv = export_csv(&frames);
+    let lines: Vec<&str> = csv.lines().collect();
+    assert_eq!(lines[0], "timestamp,session_id,speaker,depth,content,emotions");
+    assert_eq!(lines[1], "2025-03-25T12:00:00+00:00,\"alice\",user,2,\"Say \"\"hi\"\", Elias\",\"pleasure=0.25;awe=0.5\"");
+}
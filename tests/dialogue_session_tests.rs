@@ -0,0 +1,82 @@
+use chrono::{Duration, Utc};
+use crate::core::dialogue_frame::{DialogueFrame, Speaker};
+use crate::core::dialogue_retrieval::{retrieve, RetrievalConfig};
+use crate::core::dialogue_sessions::DialogueSessions;
+use crate::core::text_pipeline::analyze;
+
+fn frame_at(content: &str, speaker: Speaker, minutes_ago: i64) -> DialogueFrame {
+    DialogueFrame { timestamp: Utc::now() - Duration::minutes(minutes_ago), ..DialogueFrame::new("s", speaker, content, 0) }
+}
+
+#[test]
+fn test_sessions_are_isolated() {
+    let sessions = DialogueSessions::new(3);
+    for i in 0..5 { sessions.record(DialogueFrame::new("alice", Speaker::User, &format!("turn {i}"), 0)); }
+    sessions.record(DialogueFrame::new("bob", Speaker::Elias, "hello bob", 0));
+    let shared = sessions.clone();
+    assert_eq!(shared.session_ids(), vec!["alice", "bob"]);
+    assert_eq!(shared.history("alice").iter().map(|frame| frame.content.as_str()).collect::<Vec<_>>(), vec!["turn 2", "turn 3", "turn 4"]);
+    assert_eq!(shared.recent("bob", 5)[0].speaker, Speaker::Elias);
+    assert!(shared.end_session("alice"));
+    assert!(!sessions.end_session("alice"));
+    assert_eq!((sessions.len("alice"), sessions.len("bob")), (0, 1));
+    assert!(sessions.history("carol").is_empty());
+}
+
+#[test]
+fn test_least_recently_used_session_is_evicted() {
+    let sessions = DialogueSessions::new(3).with_max_sessions(2);
+    sessions.record(DialogueFrame::new("alice", Speaker::User, "hi", 0));
+    sessions.record(DialogueFrame::new("bob", Speaker::User, "hi", 0));
+    // Reading alice makes bob the least recently used.
+    assert_eq!(sessions.recent("alice", 1).len(), 1);
+    sessions.record(DialogueFrame::new("carol", Speaker::User, "hi", 0));
+    assert_eq!(sessions.session_ids(), vec!["alice", "carol"]);
+}
+
+#[test]
+fn test_recording_races_ending_the_session() {
+    let sessions = DialogueSessions::new(4).with_max_sessions(8);
+    std::thread::scope(|scope| {
+        for worker in 0..4 {
+            let sessions = sessions.clone();
+            scope.spawn(move || {
+                for i in 0..500 {
+                    let session_id = format!("s{}", (worker + i) % 12);
+                    sessions.record(DialogueFrame::new(&session_id, Speaker::User, "turn", 0));
+                    sessions.end_session(&format!("s{}", (worker + i + 1) % 12));
+                }
+            });
+        }
+    });
+    assert!(sessions.session_ids().len() <= 8);
+}
+
+#[test]
+fn test_bm25_ranks_rare_terms_and_shorter_matches_higher() {
+    let history = vec![
+        frame_at("the network is quiet and the network is calm tonight as it always is", Speaker::User, 5),
+        frame_at("a strange attractor appeared in the network", Speaker::User, 5),
+        frame_at("the network grew", Speaker::Elias, 5),
+        frame_at("nothing related here", Speaker::User, 5),
+    ];
+    let config = RetrievalConfig { recency_weight: 0.0, ..RetrievalConfig::new() };
+    let results = retrieve(&history, &analyze("Is the attractor part of the network?"), &config, Utc::now(), 10);
+    let contents: Vec<&str> = results.iter().map(|retrieved| retrieved.frame.content.as_str()).collect();
+    assert_eq!(contents, vec!["a strange attractor appeared in the network", "the network grew", "the network is quiet and the network is calm tonight as it always is"]);
+    assert_eq!(results[0].similarity, 1.0);
+    assert!(results.windows(2).all(|pair| pair[0].score >= pair[1].score));
+    assert!(retrieve(&history, &analyze("hello"), &config, Utc::now(), 10).is_empty());
+    assert_eq!(retrieve(&history, &analyze("network"), &config, Utc::now(), 1).len(), 1);
+}
+
+#[test]
+fn test_recency_breaks_ties_and_decays() {
+    let history = vec![frame_at("entropy spikes", Speaker::User, 60), frame_at("entropy spikes", Speaker::Elias, 10), frame_at("entropy spikes", Speaker::User, 10)];
+    let config = RetrievalConfig { half_life_secs: 600.0, ..RetrievalConfig::new() };
+    let results = retrieve(&history, &analyze("entropy"), &config, Utc::now(), 3);
+    assert_eq!(results[0].frame.speaker, Speaker::User);
+    assert!((results[0].recency - 0.5).abs() < 1e-3);
+    assert!((results[2].recency - 0.5f64.powi(6)).abs() < 1e-3);
+    assert!((results[2].score - (0.7 + 0.3 * 0.5f64.powi(6))).abs() < 1e-3);
+}
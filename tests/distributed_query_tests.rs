@@ -0,0 +1,82 @@
+use std::sync::Arc;
+use std::time::Duration;
+use crate::network::distributed_query::{CombineStrategy, FanOutConfig, QueryFanOut, Responder};
+use crate::network::local_api::QueryRequest;
+use crate::network::peer_discovery::PeerDiscovery;
+
+fn responder(answer: &'static str, delay_ms: u64) -> Responder {
+    Arc::new(move |request: QueryRequest| {
+        Box::pin(async move {
+            tokio::time::sleep(Duration::from_millis(delay_ms)).await;
+            format!("{} ({})", answer, request.session_id)
+        })
+    })
+}
+
+fn ids(names: &[&str]) -> Vec<String> { names.iter().map(|name| name.to_string()).collect() }
+
+#[test]
+fn test_closest_peers_by_hops_then_xor() {
+    PeerDiscovery::record_links("fan_a", &ids(&["fan_b", "fan_c"]));
+    PeerDiscovery::record_links("fan_b", &ids(&["fan_d"]));
+    PeerDiscovery::record_links("fan_d", &ids(&["fan_e"]));
+    let candidates = ids(&["fan_e", "fan_unlinked", "fan_d", "fan_c", "fan_b", "fan_a"]);
+    let closest = PeerDiscovery::closest_peers("fan_a", &candidates, 10);
+    let mut direct = closest[..2].to_vec();
+    direct.sort();
+    assert_eq!(direct, ids(&["fan_b", "fan_c"]));
+    assert_eq!(closest[2..], ids(&["fan_d", "fan_e", "fan_unlinked"]));
+    assert_eq!(PeerDiscovery::closest_peers("fan_a", &candidates, 1), closest[..1]);
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_coherence_fan_out_with_timeouts_and_provenance() {
+    QueryFanOut::register("coh_good", responder("The Lorenz attractor has two lobes, and the attractor never settles anywhere.", 10));
+    QueryFanOut::register("coh_terse", responder("Hm.", 5));
+    QueryFanOut::register("coh_slow", responder("The Lorenz attractor is a butterfly of two lobes and chaos.", 400));
+    let peers = ids(&["coh_slow", "coh_good", "coh_missing", "coh_terse"]);
+    assert_eq!(QueryFanOut::reachable(&peers), ids(&["coh_slow", "coh_good", "coh_terse"]));
+    let config = FanOutConfig { timeout: Duration::from_millis(100), ..FanOutConfig::new(3) };
+    let answer = QueryFanOut::fan_out("coh_origin", "Describe the Lorenz attractor", async { "I am not sure.".to_string() }, &peers, &config).await;
+    let provenance = &answer.provenance;
+    assert_eq!(answer.response, "The Lorenz attractor has two lobes, and the attractor never settles anywhere. (peer:coh_origin)");
+    assert_eq!(provenance.chosen, "coh_good");
+    assert_eq!(provenance.candidates.iter().map(|candidate| candidate.peer_id.as_str()).collect::<Vec<_>>(), vec!["coh_origin", "coh_good", "coh_terse"]);
+    assert_eq!(provenance.candidates[1].latency, Duration::from_millis(10));
+    assert_eq!((provenance.timed_out.clone(), provenance.unreachable.clone()), (ids(&["coh_slow"]), ids(&["coh_missing"])));
+    QueryFanOut::unregister("coh_good");
+    assert_eq!(QueryFanOut::reachable(&peers), ids(&["coh_slow", "coh_terse"]));
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_vote_picks_consensus() {
+    QueryFanOut::register("vote_1", responder("entropy rises when the field heats", 1));
+    QueryFanOut::register("vote_2", responder("entropy rises when the field heats up", 1));
+    QueryFanOut::register("vote_3", responder("purple elephants dance", 1));
+    let config = FanOutConfig { strategy: CombineStrategy::Vote, ..FanOutConfig::new(3) };
+    let local = async { "the field heats so entropy rises (peer:vote_origin)".to_string() };
+    let answer = QueryFanOut::fan_out("vote_origin", "why does entropy rise", local, &ids(&["vote_1", "vote_2", "vote_3"]), &config).await;
+    assert_eq!(answer.provenance.chosen, "vote_1");
+    assert_eq!(answer.provenance.supporters, ids(&["vote_origin", "vote_1", "vote_2"]));
+    assert!(answer.provenance.candidates[3].score < answer.provenance.candidates[2].score);
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_fan_out_and_ranking_see_state_from_another_thread() {
+    // Registered and linked from the test's own thread; ranking and fan-out run on a runtime worker.
+    QueryFanOut::register("worker_peer", responder("entropy rises", 1));
+    PeerDiscovery::record_links("worker_origin", &ids(&["worker_hop"]));
+    PeerDiscovery::record_links("worker_hop", &ids(&["worker_peer"]));
+    let answer = tokio::spawn(async {
+        let peers = ids(&["worker_peer"]);
+        assert_eq!(QueryFanOut::reachable(&peers), peers);
+        let candidates = ids(&["worker_unlinked", "worker_peer", "worker_hop"]);
+        assert_eq!(PeerDiscovery::closest_peers("worker_origin", &candidates, 3), ids(&["worker_hop", "worker_peer", "worker_unlinked"]));
+        QueryFanOut::fan_out("worker_origin", "why", async { "local".to_string() }, &peers, &FanOutConfig::new(1)).await
+    })
+    .await
+    .unwrap();
+    QueryFanOut::unregister("worker_peer");
+    assert!(answer.provenance.unreachable.is_empty());
+    assert_eq!(answer.provenance.candidates.len(), 2);
+}
@@ -0,0 +1,90 @@
+use crate::dynamics::chaos_source::{ChaosSource, Coupling};
+use crate::dynamics::chaotic_systems::{ChaoticSystem, Chua, DiscreteMap, DoublePendulum, HenonMap, Lorenz, LogisticMap, Rossler};
+use crate::dynamics::integrators::{AdaptiveRk45, OdeSystem, Rk4};
+
+struct Oscillator;
+
+impl OdeSystem for Oscillator {
+    fn dimension(&self) -> usize { 2 }
+
+    fn derivative(&self, _t: f64, s: &[f64], out: &mut [f64]) {
+        out[0] = s[1];
+        out[1] = -s[0];
+    }
+}
+
+#[test]
+fn test_integrators_track_the_harmonic_oscillator() {
+    let mut fixed = [1.0, 0.0];
+    Rk4::new(0.01).integrate(&Oscillator, 0.0, &mut fixed, 10.0);
+    assert!((fixed[0] - 10f64.cos()).abs() < 1e-8 && (fixed[1] + 10f64.sin()).abs() < 1e-8);
+
+    let mut adaptive = [1.0, 0.0];
+    let mut integrator = AdaptiveRk45::new(1e-10);
+    integrator.max_step = 1.0;
+    integrator.integrate(&Oscillator, 0.0, &mut adaptive, 10.0);
+    assert!((adaptive[0] - 10f64.cos()).abs() < 1e-7 && (adaptive[1] + 10f64.sin()).abs() < 1e-7);
+    // Error control settles on steps several times larger than the fixed scheme needed.
+    assert!(integrator.step > 0.02);
+}
+
+#[test]
+fn test_attractors_are_bounded_and_sensitive() {
+    for system in [ChaoticSystem::Lorenz(Lorenz::classic()), ChaoticSystem::Rossler(Rossler::classic()), ChaoticSystem::Chua(Chua::double_scroll())] {
+        let (mut a, mut b) = (system.initial_state(), system.initial_state());
+        b[0] += 1e-9;
+        let mut integrator = AdaptiveRk45::new(1e-10);
+        let mut other = integrator.clone();
+        integrator.integrate(&system, 0.0, &mut a, 300.0);
+        other.integrate(&system, 0.0, &mut b, 300.0);
+        assert!(a.iter().all(|x| x.abs() < 100.0), "{} escaped: {a:?}", system.name());
+        let separation = a.iter().zip(&b).map(|(x, y)| (x - y).powi(2)).sum::<f64>().sqrt();
+        assert!(separation > 1e-3, "{} did not diverge: {separation}", system.name());
+    }
+}
+
+#[test]
+fn test_double_pendulum_conserves_energy() {
+    let pendulum = DoublePendulum::unit();
+    let mut state = ChaoticSystem::DoublePendulum(pendulum).initial_state();
+    let before = pendulum.energy(&state);
+    AdaptiveRk45::new(1e-10).integrate(&pendulum, 0.0, &mut state, 20.0);
+    assert!((pendulum.energy(&state) - before).abs() < 1e-5 * before.abs());
+}
+
+#[test]
+fn test_maps_have_positive_lyapunov_exponents() {
+    // Logistic map at r = 4 has exponent ln 2.
+    let logistic = LogisticMap { r: 4.0 };
+    let mut x = [0.3];
+    let mut sum = 0.0;
+    for _ in 0..100_000 {
+        logistic.iterate(&mut x);
+        sum += (logistic.r * (1.0 - 2.0 * x[0])).abs().ln();
+    }
+    assert!((sum / 100_000.0 - std::f64::consts::LN_2).abs() < 0.02);
+
+    let henon = HenonMap::classic();
+    let mut state = [0.1, 0.1];
+    for _ in 0..10_000 {
+        henon.iterate(&mut state);
+        assert!(state[0].abs() < 1.5 && state[1].abs() < 0.5);
+    }
+}
+
+#[test]
+fn test_chaos_source_is_deterministic_and_coupled() {
+    let mut a = ChaosSource::new(ChaoticSystem::Lorenz(Lorenz::classic()));
+    let mut b = a.clone();
+    assert_eq!(a.trajectory(50), b.trajectory(50));
+
+    let mut quiet = ChaosSource::new(ChaoticSystem::Henon(HenonMap::classic()));
+    quiet.coupling = Coupling::none();
+    let mut busy = quiet.clone();
+    busy.coupling = Coupling { peer_weight: 1.0, field_weight: 0.0 };
+    quiet.step(1000, None);
+    busy.step(1000, None);
+    assert_eq!(quiet.time(), 1.0);
+    assert_eq!(busy.time(), (1.0 + 1001f64.ln()).round());
+    assert_ne!(quiet.state(), busy.state());
+}
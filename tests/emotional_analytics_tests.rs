@@ -0,0 +1,76 @@
+use chrono::{DateTime, Duration, TimeZone, Utc};
+use crate::core::emotional_analytics::Mood;
+use crate::core::emotional_state_model::{Appraisal, CouplingWeights, Dimension, DimensionConfig, DimensionError, EmotionalStateModel, Signal};
+
+// Undecaying PAD model where sentiment moves pleasure and network health moves dominance 1:1.
+fn model() -> EmotionalStateModel {
+    let mut model = EmotionalStateModel::new();
+    for dimension in [Dimension::Pleasure, Dimension::Arousal, Dimension::Dominance] {
+        model.add_dimension(dimension, DimensionConfig { baseline: 0.0, min: -1.0, max: 1.0, half_life_secs: f64::INFINITY }).unwrap();
+    }
+    model.set_coupling(CouplingWeights::empty().with(Signal::QuerySentiment, Dimension::Pleasure, 1.0).with(Signal::NetworkHealth, Dimension::Dominance, 1.0));
+    model
+}
+
+fn at(second: i64) -> DateTime<Utc> { Utc.with_ymd_and_hms(2024, 5, 1, 12, 0, 0).unwrap() + Duration::seconds(second) }
+
+fn nudge(model: &mut EmotionalStateModel, second: i64, pleasure: f64) {
+    model.appraise_at(&Appraisal { query_sentiment: Some(pleasure), ..Default::default() }, at(second));
+}
+
+#[test]
+fn test_moving_average_and_volatility() {
+    let mut model = model();
+    for second in 0..10 { nudge(&mut model, second, 0.1); }
+    for second in 10..20 { nudge(&mut model, second, if second % 2 == 0 { 0.2 } else { -0.2 }); }
+    let averages = model.moving_average(&Dimension::Pleasure, 3);
+    assert_eq!(averages.len(), 20);
+    assert!((averages[9].1 - 0.9).abs() < 1e-9);
+    assert!((averages[0].1 - 0.1).abs() < 1e-9);
+    assert!(model.volatility(&Dimension::Pleasure, at(0), at(10)) < 1e-9);
+    assert!(model.volatility(&Dimension::Pleasure, at(10), at(20)) > 0.2);
+}
+
+#[test]
+fn test_dominant_mood_over_window() {
+    let mut model = model();
+    for second in 0..5 { nudge(&mut model, second, 0.1); }
+    model.appraise_at(&Appraisal { network_health: Some(0.0), ..Default::default() }, at(5));
+    for second in 6..9 { nudge(&mut model, second, 0.0); }
+    assert_eq!(model.dominant_mood(at(0), at(5)), Some(Mood::Exuberant));
+    assert_eq!(model.dominant_mood(at(5), at(9)), Some(Mood::Dependent));
+    assert_eq!(model.dominant_mood(at(100), at(200)), None);
+}
+
+#[test]
+fn test_cusum_finds_level_shift() {
+    let mut model = model();
+    for second in 0..30 { nudge(&mut model, second, if second == 15 { 0.8 } else if second % 2 == 0 { 0.01 } else { -0.01 }); }
+    assert_eq!(model.change_points(&Dimension::Pleasure, 0.5, 0.05), vec![at(15)]);
+}
+
+#[test]
+fn test_export_csv_and_json() {
+    let mut model = model();
+    model.add_dimension(Dimension::Custom("curiosity, \"raw\"".to_string()), DimensionConfig::unipolar(10.0)).unwrap();
+    for second in 0..3 { nudge(&mut model, second, 0.25); }
+    let csv = model.export_csv();
+    let lines: Vec<&str> = csv.lines().collect();
+    assert_eq!(lines[0], "timestamp,\"pleasure\",\"arousal\",\"dominance\",\"cosmic_resonance\",\"curiosity, \"\"raw\"\"\"");
+    assert_eq!(lines.len(), 4);
+    assert!(lines[3].starts_with(&at(2).to_rfc3339()) && lines[3].contains(",0.75,"));
+
+    let json: serde_json::Value = serde_json::from_str(&model.export_json()).unwrap();
+    assert_eq!(json.as_array().unwrap().len(), 3);
+    assert_eq!(json[2]["dimensions"]["pleasure"], 0.75);
+    assert_eq!(json[2]["mood"], "Exuberant");
+}
+
+#[test]
+fn test_custom_dimension_cannot_shadow_a_built_in() {
+    let mut model = model();
+    let error = model.add_dimension(Dimension::Custom("pleasure".to_string()), DimensionConfig::unipolar(10.0)).unwrap_err();
+    assert_eq!(error, DimensionError::ReservedLabel("pleasure".to_string()));
+    assert_eq!(error.to_string(), "custom dimension label \"pleasure\" is reserved");
+    assert_eq!(model.dimensions().count(), 4);
+}
@@ -0,0 +1,89 @@
+use crate::core::emotional_contagion::ContagionConfig;
+use crate::core::emotional_state_model::{Dimension, EmotionalStateModel};
+use crate::network::cosmic_gossip_protocol::CosmicGossipProtocol;
+
+// One gossip round on a ring: every node sends its summary to both neighbours, then blends
+// whatever reached it. Node 0 is pinned to a joyful mood.
+async fn ring_round(nodes: &mut [(String, EmotionalStateModel)], config: &ContagionConfig) {
+    let gossip = CosmicGossipProtocol::new();
+    let n = nodes.len();
+    for (peer_id, _) in nodes.iter() { CosmicGossipProtocol::listen(peer_id); }
+    for i in 0..n {
+        let neighbors = vec![nodes[(i + 1) % n].0.clone(), nodes[(i + n - 1) % n].0.clone()];
+        assert_eq!(gossip.gossip_emotion(&nodes[i].1.summary(&nodes[i].0), &neighbors, 0.0).await, 2);
+    }
+    for (peer_id, model) in nodes.iter_mut() {
+        model.blend_with_neighbors(&CosmicGossipProtocol::take_emotions(peer_id), config);
+    }
+    nodes[0].1.set(&Dimension::Pleasure, 0.9);
+}
+
+#[tokio::test]
+async fn test_mood_propagates_around_the_ring() {
+    let mut nodes: Vec<(String, EmotionalStateModel)> = (0..6).map(|i| (format!("contagion_{}", i), EmotionalStateModel::new())).collect();
+    nodes[0].1.set(&Dimension::Pleasure, 0.9);
+    let config = ContagionConfig::new(0.5);
+    ring_round(&mut nodes, &config).await;
+    // After one round only the direct neighbours have caught the mood.
+    assert!(nodes[1].1.get_current_valence() > 0.2 && nodes[5].1.get_current_valence() > 0.2);
+    assert_eq!(nodes[3].1.get_current_valence(), 0.0);
+    for _ in 0..60 { ring_round(&mut nodes, &config).await; }
+    assert!(nodes.iter().all(|(_, model)| model.get_current_valence() > 0.8));
+    assert!(CosmicGossipProtocol::take_emotions("contagion_3").is_empty());
+}
+
+#[tokio::test]
+async fn test_trust_and_susceptibility() {
+    let gossip = CosmicGossipProtocol::new();
+    CosmicGossipProtocol::listen("listener");
+    let (mut happy, mut sad) = (EmotionalStateModel::new(), EmotionalStateModel::new());
+    happy.set(&Dimension::Pleasure, 1.0);
+    sad.set(&Dimension::Pleasure, -1.0);
+    gossip.gossip_emotion(&happy.summary("friend"), &["listener".to_string()], 0.0).await;
+    gossip.gossip_emotion(&sad.summary("stranger"), &["listener".to_string()], 0.0).await;
+    let received = CosmicGossipProtocol::take_emotions("listener");
+    assert_eq!(received.len(), 2);
+
+    let mut listener = EmotionalStateModel::new();
+    listener.blend_with_neighbors(&received, &ContagionConfig::new(0.0));
+    assert_eq!(listener.get_current_valence(), 0.0);
+    // Trust 3:1 puts the weighted mean at 0.5; full susceptibility jumps straight to it.
+    listener.blend_with_neighbors(&received, &ContagionConfig::new(1.0).with_trust("friend", 3.0));
+    assert!((listener.get_current_valence() - 0.5).abs() < 1e-12);
+    let mut wary = EmotionalStateModel::new();
+    wary.blend_with_neighbors(&received, &ContagionConfig::new(1.0).with_trust("stranger", 0.0));
+    assert!((wary.get_current_valence() - 1.0).abs() < 1e-12);
+    assert_eq!(wary.history().len(), 1);
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn test_gossip_reaches_tasks_on_other_workers() {
+    CosmicGossipProtocol::listen("worker_listener");
+    let senders: Vec<_> = (0..8)
+        .map(|i| {
+            tokio::spawn(async move {
+                let mut model = EmotionalStateModel::new();
+                model.set(&Dimension::Pleasure, 0.5);
+                CosmicGossipProtocol::new().gossip_emotion(&model.summary(&format!("worker_sender_{}", i)), &["worker_listener".to_string()], 0.0).await
+            })
+        })
+        .collect();
+    for sender in senders { assert_eq!(sender.await.unwrap(), 1); }
+    let received = tokio::spawn(async { CosmicGossipProtocol::take_emotions("worker_listener") }).await.unwrap();
+    assert_eq!(received.len(), 8);
+}
+
+#[tokio::test]
+async fn test_only_listening_peers_receive_summaries() {
+    let summary = EmotionalStateModel::new().summary("inbox_sender");
+    let peers: Vec<String> = (0..3).map(|i| format!("global_{}", i)).chain(std::iter::once("inbox_listener".to_string())).collect();
+    assert_eq!(CosmicGossipProtocol::new().gossip_emotion(&summary, &peers, 0.0).await, 0);
+    CosmicGossipProtocol::listen("inbox_listener");
+    assert_eq!(CosmicGossipProtocol::new().gossip_emotion(&summary, &peers, 0.0).await, 1);
+    assert_eq!(CosmicGossipProtocol::take_emotions("inbox_listener"), vec![summary.clone()]);
+    assert!(CosmicGossipProtocol::take_emotions("global_0").is_empty());
+    CosmicGossipProtocol::stop_listening("inbox_listener");
+    assert_eq!(CosmicGossipProtocol::new().gossip_emotion(&summary, &peers, 0.0).await, 0);
+    assert_eq!(summary.get(&Dimension::Custom("awe".to_string())), None);
+    assert_eq!(summary.get(&Dimension::CosmicResonance), Some(summary.cosmic_resonance));
+}
@@ -1,7 +1,41 @@
+use chrono::{Duration, TimeZone, Utc};
+use crate::core::emotional_state_model::{Appraisal, CouplingWeights, Dimension, DimensionConfig, EmotionalStateModel, Signal};
+
 #[test]
 fn test_cosmic_feedback() {
     let mut model = crate::core::emotional_state_model::EmotionalStateModel::new();
-    let tensor = crate::quantum::quantum_fractal_tensor_engine::QuantumFractalTensorEngine::new();
+    let mut tensor = crate::quantum::quantum_fractal_tensor_engine::QuantumFractalTensorEngine::new();
+    tensor.cosmic_entropy = 2.0;
     model.adjust_with_cosmic_feedback(&tensor);
-    assert!(*model.emotional_dimensions.get("cosmic_resonance").unwrap() > 0.0);
+    assert!(model.get_cosmic_resonance() > 0.0);
+}
+
+#[test]
+fn test_values_stay_bounded_and_decay_to_baseline() {
+    let mut model = EmotionalStateModel::new();
+    model.add_dimension(Dimension::Custom("curiosity".to_string()), DimensionConfig { baseline: 0.5, min: 0.0, max: 1.0, half_life_secs: 10.0 }).unwrap();
+    model.set_coupling(CouplingWeights::empty().with(Signal::QuerySentiment, Dimension::Pleasure, 0.6).with(Signal::QuerySentiment, Dimension::Custom("curiosity".to_string()), -0.3));
+    let start = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+    let joyful = Appraisal { query_sentiment: Some(1.0), ..Default::default() };
+    for i in 0..10 { model.appraise_at(&joyful, start + Duration::milliseconds(i)); }
+    assert!((model.get_current_valence() - 1.0).abs() < 1e-12);
+    assert!((model.get(&Dimension::Custom("curiosity".to_string())) - 0.0).abs() < 1e-12);
+
+    // One Pleasure half-life (60s) later with a neutral appraisal: halfway back to 0.
+    model.appraise_at(&Appraisal::default(), start + Duration::milliseconds(9) + Duration::seconds(60));
+    assert!((model.get_current_valence() - 0.5).abs() < 1e-9);
+    // Six curiosity half-lives: within 1/64 of its baseline, approached from below.
+    let curiosity = model.get(&Dimension::Custom("curiosity".to_string()));
+    assert!((curiosity - (0.5 - 0.5 / 64.0)).abs() < 1e-9);
+}
+
+#[test]
+fn test_appraisal_combines_inputs_through_coupling_weights() {
+    let mut model = EmotionalStateModel::new();
+    model.appraise(&Appraisal { query_sentiment: Some(-1.0), network_health: Some(1.0), ..Default::default() });
+    // Pleasure: -0.2 from sentiment + 0.05 from health; Dominance: +0.1 from health.
+    assert!((model.get_current_valence() + 0.15).abs() < 1e-9);
+    assert!((model.get(&Dimension::Dominance) - 0.1).abs() < 1e-9);
+    assert!((model.get(&Dimension::Arousal) + 0.05).abs() < 1e-9);
+    assert_eq!(model.get_cosmic_resonance(), 0.0);
 }
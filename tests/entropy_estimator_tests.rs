@@ -0,0 +1,74 @@
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use crate::quantum::entropy_estimators::{EntropyConfig, EntropyInput, EntropyUnit, SAMPLE_ENTROPY_WINDOW};
+
+fn white_noise(len: usize, seed: u64) -> Vec<f64> {
+    let mut rng = StdRng::seed_from_u64(seed);
+    (0..len).map(|_| rng.gen::<f64>()).collect()
+}
+
+#[test]
+fn test_shannon_field_entropy_of_known_distributions() {
+    let estimator = EntropyConfig::ShannonField { bins: 16 }.build();
+    assert_eq!(estimator.unit(), EntropyUnit::Bits);
+    let uniform: Vec<f64> = (0..1600).map(f64::from).collect();
+    assert!((estimator.estimate(&EntropyInput { field: &uniform, ..Default::default() }) - 4.0).abs() < 1e-12);
+    let two_point: Vec<f64> = (0..100).map(|i| f64::from(i % 2)).collect();
+    assert!((estimator.estimate(&EntropyInput { field: &two_point, ..Default::default() }) - 1.0).abs() < 1e-12);
+    assert_eq!(estimator.estimate(&EntropyInput { field: &[0.3; 50], ..Default::default() }), 0.0);
+}
+
+#[test]
+fn test_permutation_entropy_bounds() {
+    let estimator = EntropyConfig::PermutationEntropy { order: 3, delay: 1, normalized: true }.build();
+    assert_eq!(estimator.unit(), EntropyUnit::Normalized);
+    let ramp: Vec<f64> = (0..200).map(f64::from).collect();
+    assert_eq!(estimator.estimate(&EntropyInput { series: &ramp, ..Default::default() }), 0.0);
+    let noise = white_noise(20_000, 7);
+    assert!(estimator.estimate(&EntropyInput { series: &noise, ..Default::default() }) > 0.99);
+    // Alternating up/down visits exactly two of the six patterns, equally often.
+    let zigzag: Vec<f64> = (0..202).map(|i| f64::from(i % 2)).collect();
+    let bits = EntropyConfig::PermutationEntropy { order: 3, delay: 1, normalized: false }.build();
+    assert!((bits.estimate(&EntropyInput { series: &zigzag, ..Default::default() }) - 1.0).abs() < 1e-12);
+}
+
+#[test]
+fn test_sample_entropy_separates_regular_from_random() {
+    let estimator = EntropyConfig::SampleEntropy { m: 2, r_factor: 0.2 }.build();
+    assert_eq!(estimator.unit(), EntropyUnit::Nats);
+    let sine: Vec<f64> = (0..500).map(|i| (i as f64 * 0.3).sin()).collect();
+    let regular = estimator.estimate(&EntropyInput { series: &sine, ..Default::default() });
+    let noise = white_noise(500, 11);
+    let random = estimator.estimate(&EntropyInput { series: &noise, ..Default::default() });
+    assert!(regular < 0.3, "{regular}");
+    // For uniform noise each extra sample matches with probability 2r - r^2, r = 0.2 / sqrt(12).
+    let r = 0.2 / 12f64.sqrt();
+    assert!((random + (2.0 * r - r * r).ln()).abs() < 0.25, "{random}");
+    // Only the newest window counts: noise older than that doesn't move the estimate.
+    let mut history = white_noise(10_000, 13);
+    history.extend(sine.iter().cycle().take(SAMPLE_ENTROPY_WINDOW));
+    let recent = &history[history.len() - SAMPLE_ENTROPY_WINDOW..];
+    assert_eq!(estimator.estimate(&EntropyInput { series: &history, ..Default::default() }), estimator.estimate(&EntropyInput { series: recent, ..Default::default() }));
+}
+
+#[test]
+fn test_degree_entropy() {
+    let estimator = EntropyConfig::DegreeEntropy.build();
+    let ring = [2usize; 10];
+    assert_eq!(estimator.estimate(&EntropyInput { peer_degrees: &ring, ..Default::default() }), 0.0);
+    let star = [4usize, 1, 1, 1, 1];
+    let expected = -(0.2f64 * 0.2f64.log2() + 0.8 * 0.8f64.log2());
+    assert!((estimator.estimate(&EntropyInput { peer_degrees: &star, ..Default::default() }) - expected).abs() < 1e-12);
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_degree_sequence_sees_links_from_other_workers() {
+    use crate::network::peer_discovery::PeerDiscovery;
+    let leaves: Vec<String> = (0..7).map(|i| format!("degree_leaf_{}", i)).collect();
+    // Recorded on a runtime worker, read back on the test's own thread.
+    tokio::spawn(async move { PeerDiscovery::record_links("degree_hub", &leaves) }).await.unwrap();
+    let degrees = PeerDiscovery::degree_sequence();
+    assert!(degrees.contains(&7), "{degrees:?}");
+    assert!(degrees.iter().filter(|&&degree| degree == 1).count() >= 7);
+    assert!(EntropyConfig::DegreeEntropy.build().estimate(&EntropyInput { peer_degrees: &degrees, ..Default::default() }) > 0.0);
+}
@@ -0,0 +1,77 @@
+use crate::quantum::field_analysis::FieldAnalyzer;
+use crate::quantum::field_rules::{CoupledLogisticLattice, Diffusion};
+use crate::quantum::field_tensor::FieldTensor;
+use crate::quantum::quantum_fractal_tensor_engine::QuantumFractalTensorEngine;
+
+#[test]
+fn test_moments_histogram_and_entropy() {
+    let analyzer = FieldAnalyzer::new(8, 2);
+    let constant = analyzer.analyze(&FieldTensor::from_data([4, 4], vec![0.5; 16]));
+    assert_eq!((constant.mean, constant.variance, constant.shannon_entropy), (0.5, 0.0, 0.0));
+    let ramp = analyzer.analyze(&FieldTensor::from_data([8, 8], (0..64).map(f64::from).collect()));
+    assert_eq!(ramp.histogram.counts, vec![8; 8]);
+    assert!((ramp.shannon_entropy - 3.0).abs() < 1e-12);
+    assert!((ramp.variance - (64.0 * 64.0 - 1.0) / 12.0).abs() < 1e-9);
+}
+
+#[test]
+fn test_spectrum_and_autocorrelation_of_a_wave() {
+    let wave: Vec<f64> = (0..32 * 32).map(|i| (2.0 * std::f64::consts::PI * 4.0 * (i % 32) as f64 / 32.0).sin()).collect();
+    let stats = FieldAnalyzer::new(16, 8).analyze(&FieldTensor::from_data([32, 32], wave));
+    let peak = (0..stats.power_spectrum.len()).max_by(|a, b| stats.power_spectrum[*a].total_cmp(&stats.power_spectrum[*b])).unwrap();
+    assert_eq!(peak, 4);
+    // Half the axes see a period-8 wave, the other half a constant.
+    assert!((stats.autocorrelation[7] - 1.0).abs() < 1e-9);
+    assert!((stats.autocorrelation[3] - 0.0).abs() < 1e-9);
+    assert!(stats.looks_structured());
+}
+
+#[test]
+fn test_box_counting_sierpinski() {
+    let size = 256;
+    let data = (0..size * size).map(|i| if (i / size) & (i % size) == 0 { 1.0 } else { 0.0 }).collect();
+    let stats = FieldAnalyzer::new(2, 1).analyze(&FieldTensor::from_data([size, size], data));
+    assert!((stats.box_counting_dimension - 3f64.log2()).abs() < 0.05);
+}
+
+#[test]
+fn test_step_growth_sign() {
+    let mut chaotic = QuantumFractalTensorEngine::with_config([32, 32], 2, 5);
+    chaotic.set_rule(CoupledLogisticLattice { r: 4.0, epsilon: 0.1 });
+    let mut smooth = QuantumFractalTensorEngine::with_config([32, 32], 2, 5);
+    smooth.set_rule(Diffusion::new(0.2));
+    for _ in 0..12 {
+        chaotic.evolve(1);
+        chaotic.observe_field();
+        smooth.evolve(1);
+        smooth.observe_field();
+    }
+    assert!(smooth.last_statistics().unwrap().log_step_growth.unwrap() < 0.0);
+    assert!(chaotic.last_statistics().unwrap().log_step_growth.unwrap() > smooth.last_statistics().unwrap().log_step_growth.unwrap());
+}
+
+#[test]
+fn test_lyapunov_exponent_of_logistic_lattices() {
+    use crate::quantum::field_analysis::lyapunov_exponent;
+    let start = FieldTensor::from_data([8, 8], (0..64).map(|i| 0.05 + 0.9 * ((i * 37) % 64) as f64 / 64.0).collect());
+    let logistic = |r: f64| move |field: &FieldTensor<2>| FieldTensor::from_data(field.shape(), field.as_slice().iter().map(|x| r * x * (1.0 - x)).collect());
+    // Fully chaotic at r = 4 with exponent ln 2; at r = 2.5 every cell settles on 0.6, where the slope is -0.5.
+    let chaotic = lyapunov_exponent(&start, 2000, 1e-9, logistic(4.0)).unwrap();
+    assert!((chaotic - 2f64.ln()).abs() < 0.05, "{chaotic}");
+    let stable = lyapunov_exponent(&start, 2000, 1e-9, logistic(2.5)).unwrap();
+    assert!((stable + 2f64.ln()).abs() < 0.05, "{stable}");
+
+    // The engine's twin follows the same estimate through sharded, noisy updates.
+    let mut engine = QuantumFractalTensorEngine::with_config([16, 16], 3, 5);
+    engine.set_rule(CoupledLogisticLattice { r: 4.0, epsilon: 0.0 });
+    assert_eq!(engine.lyapunov_exponent(), None);
+    engine.track_lyapunov(1e-9);
+    engine.evolve(2000);
+    let estimate = engine.observe_field().lyapunov_exponent.unwrap();
+    assert!((estimate - 2f64.ln()).abs() < 0.05, "{estimate}");
+    let mut smooth = QuantumFractalTensorEngine::with_config([16, 16], 2, 5);
+    smooth.set_rule(Diffusion::new(0.2));
+    smooth.track_lyapunov(1e-9);
+    smooth.evolve(200);
+    assert!(smooth.lyapunov_exponent().unwrap() < 1e-6);
+}
@@ -0,0 +1,54 @@
+use num_complex::Complex64;
+use crate::quantum::fractal_generators::{FractalGenerator, FractalKind, FractalParams};
+
+#[test]
+fn test_escape_time_samples() {
+    let mandelbrot = FractalGenerator::new(FractalParams::new(FractalKind::Mandelbrot));
+    assert_eq!(mandelbrot.sample(Complex64::new(0.0, 0.0)), 1.0);
+    assert!(mandelbrot.sample(Complex64::new(2.0, 2.0)) < 0.1);
+    let burning_ship = FractalGenerator::new(FractalParams::new(FractalKind::BurningShip));
+    assert_eq!(burning_ship.sample(Complex64::new(-0.1, -0.1)), 1.0);
+    let newton = FractalGenerator::new(FractalParams::new(FractalKind::Newton));
+    let near_one = newton.sample(Complex64::new(1.1, 0.0));
+    assert!(near_one > 0.0 && near_one <= 1.0 / 3.0);
+    assert!(newton.sample(Complex64::new(-0.6, 0.9)) > 1.0 / 3.0);
+}
+
+#[test]
+fn test_node_state_is_reproducible() {
+    let history = [0.3, 1.7, 2.2, 0.9];
+    let a = FractalParams::from_node_state(FractalKind::Julia, 42.0, 0.4, &history);
+    let b = FractalParams::from_node_state(FractalKind::Julia, 42.0, 0.4, &history);
+    let c = FractalParams::from_node_state(FractalKind::Julia, 42.0, 0.4, &history[..3]);
+    assert_eq!(a, b);
+    assert_ne!(a.seed, c.seed);
+    for kind in [FractalKind::Julia, FractalKind::Flame] {
+        let params = FractalParams { kind, ..a.clone() };
+        let image = FractalGenerator::new(params.clone()).render(24, 16);
+        assert_eq!(image, FractalGenerator::new(params).render(24, 16));
+        assert!(image.iter().all(|v| (0.0..=1.0).contains(v)));
+        assert!(image.iter().any(|v| *v > 0.0));
+    }
+}
+
+#[test]
+fn test_seed_tensor_field() {
+    let mut engine = crate::quantum::quantum_fractal_tensor_engine::QuantumFractalTensorEngine::with_config([32, 48], 2, 1);
+    engine.seed_with_fractal(&FractalParams::new(FractalKind::Mandelbrot));
+    assert_eq!(engine.tensor_field[[16, 16]], 1.0);
+    assert!(engine.tensor_field[[0, 0]] < 0.5);
+    engine.modulate_with_fractal(&FractalParams::new(FractalKind::Mandelbrot), 0.5);
+    assert_eq!(engine.tensor_field[[16, 16]], 1.0);
+}
+
+#[test]
+fn test_prerendered_image_blends_like_modulate() {
+    let params = FractalParams::new(FractalKind::Julia);
+    let mut engine = crate::quantum::quantum_fractal_tensor_engine::QuantumFractalTensorEngine::with_config([24, 40], 2, 1);
+    let mut prerendered = engine.clone();
+    engine.modulate_with_fractal(&params, 0.3);
+    let image = FractalGenerator::new(params).render_for(prerendered.tensor_field.shape());
+    assert_eq!(image.len(), 24 * 40);
+    prerendered.blend_fractal_image(&image, 0.3);
+    assert_eq!(prerendered.tensor_field.as_slice(), engine.tensor_field.as_slice());
+}
@@ -0,0 +1,35 @@
+use std::sync::{Arc, Mutex};
+use crate::core::dialogue_sessions::DEFAULT_SESSION;
+use crate::core::elias_nlp_interface::EliasNLPInterface;
+use crate::core::emotional_state_model::Dimension;
+use crate::core::self_model::{SelfModel, SelfObservation};
+
+fn observed(pleasure: f64) -> Arc<Mutex<SelfModel>> {
+    let self_model = Arc::new(Mutex::new(SelfModel::new()));
+    self_model.lock().unwrap().observe(SelfObservation { entropy: 3.0, pleasure, arousal: 0.6, dominance: 0.4, cosmic_entropy: 1.5 });
+    self_model
+}
+
+#[tokio::test]
+async fn test_answers_follow_the_shared_self_model() {
+    let nli = EliasNLPInterface::new();
+    let self_model = observed(0.8);
+    let (_, trace) = nli.process_query_traced(DEFAULT_SESSION, "How are you?".to_string(), 0, self_model.clone()).await;
+    let draft = &trace.attempts[0].candidate;
+    assert!(draft.contains("exuberant (valence +0.80) at entropy 3.00, cosmic 1.50"), "{draft}");
+    // The owner observes a new state; the next answer sees it through the same handle.
+    self_model.lock().unwrap().observe(SelfObservation { pleasure: -0.5, arousal: 0.6, dominance: 0.4, ..Default::default() });
+    let (_, trace) = nli.process_query_traced(DEFAULT_SESSION, "How are you now?".to_string(), 0, self_model).await;
+    assert!(trace.attempts[0].candidate.contains("hostile (valence -0.50)"), "{}", trace.attempts[0].candidate);
+}
+
+#[tokio::test]
+async fn test_frames_log_the_latest_observation() {
+    let nli = EliasNLPInterface::new();
+    nli.process_query("hello".to_string(), 0, observed(0.8)).await;
+    let latest = observed(-0.3).lock().unwrap().latest();
+    nli.record_turn(DEFAULT_SESSION, "hello", "cached", 0, &latest);
+    let pleasure: Vec<f64> = nli.recent_dialogue(DEFAULT_SESSION, 4).iter().map(|frame| frame.emotions[&Dimension::Pleasure]).collect();
+    assert_eq!(pleasure, [0.8, 0.8, -0.3, -0.3]);
+    assert_eq!(nli.recent_dialogue(DEFAULT_SESSION, 1)[0].emotions[&Dimension::Dominance], 0.4);
+}
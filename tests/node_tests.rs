@@ -10,3 +10,28 @@ async fn test_query_processing() {
     let response = node.process_query("Hello".to_string()).await;
     assert!(response.contains("v4.4.1"));
 }
+
+#[tokio::test]
+async fn test_introspection_sees_the_sync_loop() {
+    let node = crate::core::self_evolving_fractal_gossip_node::SelfEvolvingFractalGossipNode::new("introspect_1".to_string()).await.unwrap();
+    // The spawned loop ticks on its own clone of the node; the self model is shared with it.
+    let deadline = tokio::time::Instant::now() + tokio::time::Duration::from_secs(5);
+    while node.introspect().observations == 0 && tokio::time::Instant::now() < deadline {
+        tokio::time::sleep(tokio::time::Duration::from_millis(20)).await;
+    }
+    let report = node.introspect();
+    assert!(report.observations > 0);
+    assert!(report.timestamp > chrono::DateTime::<chrono::Utc>::MIN_UTC);
+}
+
+#[tokio::test]
+async fn test_cached_and_fresh_turns_are_logged() {
+    let node = crate::core::self_evolving_fractal_gossip_node::SelfEvolvingFractalGossipNode::new("cached_1".to_string()).await.unwrap();
+    let first = node.process_query("What is entropy?".to_string()).await;
+    let second = node.process_query("What is entropy?".to_string()).await;
+    // The sync loop may move the epoch between the two queries; either way both turns are logged.
+    let metrics = node.query_cache().metrics();
+    assert_eq!(metrics.hits + metrics.misses, 2);
+    let contents: Vec<String> = node.nli().recent_dialogue("default", 4).into_iter().map(|frame| frame.content).collect();
+    assert_eq!(contents, ["What is entropy?".to_string(), first, "What is entropy?".to_string(), second]);
+}
@@ -0,0 +1,84 @@
+#![cfg(feature = "openai-adapter")]
+
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
+use crate::core::emotional_analytics::Mood;
+use crate::core::response_generators::{OpenAiCompatibleGenerator, ResponseGenerator};
+use crate::core::response_templates::{render, ResponseContext};
+use crate::core::text_pipeline::analyze;
+
+// Serves one canned chat-completions reply and hands back the raw request it received.
+async fn mock_server(reply: &'static str) -> (String, tokio::task::JoinHandle<String>) {
+    let body = format!(r#"{{"choices":[{{"message":{{"role":"assistant","content":"{}"}}}}]}}"#, reply);
+    serve_once("application/json", body).await
+}
+
+async fn serve_once(content_type: &'static str, body: String) -> (String, tokio::task::JoinHandle<String>) {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    let handle = tokio::spawn(async move {
+        let (mut socket, _) = listener.accept().await.unwrap();
+        let mut request = Vec::new();
+        let mut buffer = [0u8; 4096];
+        loop {
+            let read = socket.read(&mut buffer).await.unwrap();
+            request.extend_from_slice(&buffer[..read]);
+            let text = String::from_utf8_lossy(&request).to_string();
+            if let Some(header_end) = text.find("\r\n\r\n") {
+                let length = text.lines().find_map(|line| line.to_lowercase().strip_prefix("content-length: ").map(|n| n.trim().parse::<usize>().unwrap())).unwrap_or(0);
+                if request.len() >= header_end + 4 + length { break; }
+            }
+        }
+        let response = format!("HTTP/1.1 200 OK\r\ncontent-type: {}\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{}", content_type, body.len(), body);
+        socket.write_all(response.as_bytes()).await.unwrap();
+        String::from_utf8(request).unwrap()
+    });
+    (url, handle)
+}
+
+#[tokio::test]
+async fn test_adapter_posts_chat_completion_and_prefixes_reply() {
+    let (url, server) = mock_server("The attractor breathes.").await;
+    let mut generator = OpenAiCompatibleGenerator::new(&url, "local-model");
+    generator.api_key = Some("secret".to_string());
+    let analysis = analyze("What does the Lorenz attractor feel like?");
+    let context = ResponseContext { analysis: &analysis, mood: Mood::Relaxed, valence: 0.3, entropy: 1.0, cosmic_entropy: 2.0, related: None, history: &[] };
+    assert_eq!(generator.generate(&context).await, "Elias v4.4.1: The attractor breathes.");
+    let request = server.await.unwrap();
+    assert!(request.starts_with("POST /v1/chat/completions HTTP/1.1"));
+    assert!(request.to_lowercase().contains("authorization: bearer secret"));
+    let body: serde_json::Value = serde_json::from_str(&request[request.find("\r\n\r\n").unwrap() + 4..]).unwrap();
+    assert_eq!(body["model"], "local-model");
+    assert_eq!(body["messages"][1]["content"], "What does the Lorenz attractor feel like?");
+    assert!(body["messages"][0]["content"].as_str().unwrap().contains("relaxed"));
+}
+
+#[tokio::test]
+async fn test_adapter_streams_deltas_as_they_arrive() {
+    let deltas = ["", " The attractor", " breathes", "."];
+    let mut body: String = deltas.iter().map(|delta| format!("data: {{\"choices\":[{{\"delta\":{{\"content\":\"{}\"}}}}]}}\n\n", delta)).collect();
+    body.push_str("data: [DONE]\n\n");
+    let (url, server) = serve_once("text/event-stream", body).await;
+    let analysis = analyze("hello");
+    let context = ResponseContext { analysis: &analysis, mood: Mood::Relaxed, valence: 0.0, entropy: 0.0, cosmic_entropy: 0.0, related: None, history: &[] };
+    let mut chunks = Vec::new();
+    let response = OpenAiCompatibleGenerator::new(&url, "m").generate_stream(&context, &mut |chunk| chunks.push(chunk)).await;
+    assert_eq!(chunks, ["Elias v4.4.1: ", "The attractor", " breathes", "."]);
+    assert_eq!(response, chunks.concat());
+    let request = server.await.unwrap();
+    let body: serde_json::Value = serde_json::from_str(&request[request.find("\r\n\r\n").unwrap() + 4..]).unwrap();
+    assert_eq!(body["stream"], true);
+}
+
+#[tokio::test]
+async fn test_adapter_falls_back_to_template_when_unreachable() {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    drop(listener);
+    let analysis = analyze("hello");
+    let context = ResponseContext { analysis: &analysis, mood: Mood::Relaxed, valence: 0.0, entropy: 0.0, cosmic_entropy: 0.0, related: None, history: &[] };
+    assert_eq!(OpenAiCompatibleGenerator::new(&url, "m").generate(&context).await, render(&context));
+    let mut chunks = Vec::new();
+    assert_eq!(OpenAiCompatibleGenerator::new(&url, "m").generate_stream(&context, &mut |chunk| chunks.push(chunk)).await, render(&context));
+    assert_eq!(chunks.join(" "), render(&context));
+}
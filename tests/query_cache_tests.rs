@@ -0,0 +1,67 @@
+use chrono::{Duration, TimeZone, Utc};
+use crate::core::query_cache::{CacheConfig, CacheMetrics, QueryCache};
+
+#[test]
+fn test_hits_share_normalized_queries_and_expire() {
+    let cache = QueryCache::new(CacheConfig { ttl: Duration::seconds(60), ..CacheConfig::new() });
+    let start = Utc.with_ymd_and_hms(2025, 3, 25, 12, 0, 0).unwrap();
+    let epoch = cache.epoch(3.4, 0.1, -0.3);
+    assert_eq!(epoch, cache.epoch(3.9, 0.2, -0.26));
+    assert_ne!(epoch, cache.epoch(4.0, 0.2, -0.26));
+    assert_eq!(cache.get_at("What is entropy?", &epoch, start), None);
+    cache.insert_at("What is entropy?", &epoch, "Elias v4.4.1 reflects.", start);
+    assert_eq!(cache.clone().get_at("  what IS   entropy? ", &epoch, start + Duration::seconds(59)).as_deref(), Some("Elias v4.4.1 reflects."));
+    assert_eq!(cache.get_at("what is entropy?", &cache.epoch(0.0, 0.0, 0.0), start), None);
+    assert_eq!(cache.get_at("what is entropy?", &epoch, start + Duration::seconds(60)), None);
+    assert!(cache.is_empty());
+    let metrics = cache.metrics();
+    assert_eq!(metrics, CacheMetrics { hits: 1, misses: 3, insertions: 1, evictions: 0, expirations: 1, invalidations: 0 });
+    assert_eq!(metrics.hit_rate(), 0.25);
+}
+
+#[test]
+fn test_least_recently_used_is_evicted() {
+    let cache = QueryCache::new(CacheConfig { capacity: 2, ..CacheConfig::new() });
+    let epoch = cache.epoch(1.0, 0.0, 0.0);
+    cache.insert("a", &epoch, "A");
+    cache.insert("b", &epoch, "B");
+    assert!(cache.get("a", &epoch).is_some());
+    cache.insert("c", &epoch, "C");
+    assert_eq!((cache.get("a", &epoch).as_deref(), cache.get("b", &epoch), cache.get("c", &epoch).as_deref()), (Some("A"), None, Some("C")));
+    assert_eq!(cache.metrics().evictions, 1);
+}
+
+#[test]
+fn test_epoch_changes_invalidate() {
+    let cache = QueryCache::new(CacheConfig::new());
+    let calm = cache.epoch(2.0, 0.5, 0.0);
+    let agitated = cache.epoch(2.0, -0.5, 0.9);
+    assert_eq!(cache.observe_epoch(calm), 0);
+    cache.insert("hello", &calm, "calm hello");
+    cache.insert("bye", &calm, "calm bye");
+    assert_eq!(cache.observe_epoch(calm), 0);
+    cache.insert("hello", &agitated, "agitated hello");
+    assert_eq!(cache.observe_epoch(agitated), 2);
+    assert_eq!(cache.get("hello", &agitated).as_deref(), Some("agitated hello"));
+    assert_eq!(cache.invalidate_all(), 1);
+    assert_eq!(cache.metrics().invalidations, 3);
+}
+
+#[test]
+fn test_reconfigure_reaches_every_clone() {
+    let cache = QueryCache::new(CacheConfig::new());
+    // As held by a node's sync loop, which observes epochs while the node's handle serves queries.
+    let looped = cache.clone();
+    cache.insert("hello", &cache.epoch(2.0, 0.1, 0.1), "hi");
+    assert_eq!(looped.reconfigure(CacheConfig { emotion_step: 0.5, ..CacheConfig::new() }), 1);
+    assert!(cache.is_empty());
+    assert_eq!(cache.config().emotion_step, 0.5);
+    assert_eq!(cache.epoch(2.0, 0.1, 0.1), looped.epoch(2.0, 0.4, 0.45));
+    let calm = cache.epoch(2.0, 0.1, 0.1);
+    assert_eq!(looped.observe_epoch(calm), 0);
+    cache.insert("hello", &calm, "calm hello");
+    assert_eq!(looped.observe_epoch(looped.epoch(2.0, 0.1, 0.1)), 0);
+    assert_eq!(looped.observe_epoch(looped.epoch(2.0, 0.6, 0.1)), 1);
+    assert_eq!(cache.get("hello", &calm), None);
+    assert_eq!(cache.metrics().invalidations, 2);
+}
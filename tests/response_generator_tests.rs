@@ -0,0 +1,51 @@
+use crate::core::dialogue_frame::{DialogueFrame, Speaker};
+use crate::core::emotional_analytics::Mood;
+use crate::core::response_generators::{MarkovGenerator, ResponseGenerator, RetrievalGenerator, TemplateGenerator};
+use crate::core::response_templates::{render, ResponseContext};
+use crate::core::text_pipeline::{analyze, QueryAnalysis};
+
+fn frames(contents: &[&str]) -> Vec<DialogueFrame> {
+    contents.iter().map(|content| DialogueFrame::new("default", Speaker::User, content, 0)).collect()
+}
+
+fn context<'a>(analysis: &'a QueryAnalysis, history: &'a [DialogueFrame]) -> ResponseContext<'a> {
+    ResponseContext { analysis, mood: Mood::Docile, valence: 0.1, entropy: 1.0, cosmic_entropy: 2.0, related: None, history }
+}
+
+#[tokio::test]
+async fn test_template_generator_matches_render() {
+    let analysis = analyze("hello");
+    let context = context(&analysis, &[]);
+    assert_eq!(TemplateGenerator.generate(&context).await, render(&context));
+    // Without incremental output the finished response is handed over a sentence at a time.
+    let mut chunks = Vec::new();
+    assert_eq!(TemplateGenerator.generate_stream(&context, &mut |chunk| chunks.push(chunk)).await, render(&context));
+    assert_eq!(chunks, crate::core::coherence::sentences(&render(&context)));
+}
+
+#[tokio::test]
+async fn test_markov_generator_walks_the_dialogue_chain() {
+    let history = frames(&["the spiral galaxy drifts through the quiet void", "a spiral arm glows", "the quiet void hums softly"]);
+    let analysis = analyze("Tell me about the spiral");
+    let context = context(&analysis, &history);
+    let generator = MarkovGenerator::new();
+    let first = generator.generate(&context).await;
+    assert_eq!(first, generator.generate(&context).await);
+    assert!(first.starts_with("Elias v4.4.1 muses: "), "{first}");
+    assert!(first.contains("spiral"));
+    let vocabulary: Vec<String> = history.iter().flat_map(|frame| frame.content.split(' ').map(str::to_string)).collect();
+    let words = first.trim_start_matches("Elias v4.4.1 muses: ").trim_end_matches('.');
+    assert!(words.split(' ').all(|word| vocabulary.iter().any(|known| known == word)));
+    // Nothing to learn from yet: falls back to the template.
+    assert_eq!(generator.generate(&empty_context(&analysis)).await, render(&empty_context(&analysis)));
+}
+
+fn empty_context(analysis: &QueryAnalysis) -> ResponseContext<'_> { context(analysis, &[]) }
+
+#[tokio::test]
+async fn test_retrieval_generator_quotes_best_match() {
+    let history = frames(&["peers dropped after the storm", "the mandelbrot zoom was mesmerising", "storm clouds again"]);
+    let analysis = analyze("Show me the mandelbrot zoom");
+    let response = RetrievalGenerator.generate(&context(&analysis, &history)).await;
+    assert_eq!(response, "Elias v4.4.1 recalls: \"the mandelbrot zoom was mesmerising\" - it touches on mandelbrot and zoom.");
+}
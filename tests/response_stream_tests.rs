@@ -0,0 +1,108 @@
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio_stream::wrappers::UnboundedReceiverStream;
+use crate::core::coherence::{refine, refine_with, CoherenceScorer, RefinementBudget, RefinementTrace, StopReason};
+use crate::core::response_stream::ResponseEvent;
+use crate::core::text_pipeline::analyze;
+use crate::network::local_api::{parse_export_request, parse_request, ExportRequest, LocalApi, QueryRequest};
+use crate::storage::dialogue_export::{export_dialogue, ExportFormat};
+
+#[test]
+fn test_refinement_observer_sees_every_attempt() {
+    let analysis = analyze("Why does entropy rise in the Julia field?");
+    let budget = RefinementBudget { max_depth: 5, max_duration: std::time::Duration::from_secs(5), target: 0.95 };
+    let mut seen = Vec::new();
+    let trace = refine_with(&CoherenceScorer::new(), &analysis, "Elias v4.4.1 reflects.".to_string(), &["Entropy rises as the Julia field loses structure."], &budget, |attempt, best| seen.push((attempt.clone(), best)));
+    assert_eq!(seen.iter().map(|(attempt, _)| attempt.clone()).collect::<Vec<_>>(), trace.attempts);
+    assert_eq!(seen.iter().rposition(|(_, best)| *best), Some(trace.best));
+    // Timings differ between runs; everything else matches the unobserved refinement.
+    let plain = refine(&CoherenceScorer::new(), &analysis, "Elias v4.4.1 reflects.".to_string(), &["Entropy rises as the Julia field loses structure."], &budget);
+    let candidates = |trace: &RefinementTrace| trace.attempts.iter().map(|attempt| (attempt.strategy, attempt.candidate.clone())).collect::<Vec<_>>();
+    assert_eq!(candidates(&plain), candidates(&trace));
+    assert_eq!((plain.best, plain.stop_reason), (trace.best, trace.stop_reason));
+}
+
+#[test]
+fn test_parse_request() {
+    let request = parse_request("GET /query?q=what+is%20entropy%3F&session=alice&depth=3 HTTP/1.1\r\nhost: x\r\n\r\n").unwrap();
+    assert_eq!(request, QueryRequest { session_id: "alice".to_string(), query: "what is entropy?".to_string(), depth: 3 });
+    assert_eq!(parse_request("GET /query?q=100%25%zz HTTP/1.1\r\n\r\n").unwrap().session_id, "default");
+    assert_eq!(parse_request("GET /query?q=100%25%zz HTTP/1.1\r\n\r\n").unwrap().query, "100%%zz");
+    assert_eq!(parse_request("GET /query?q=hi&depth=18446744073709551615 HTTP/1.1\r\n\r\n").unwrap().depth, 15);
+    assert!(parse_request("GET /query?session=alice HTTP/1.1\r\n\r\n").is_none());
+    assert!(parse_request("POST /query?q=hi HTTP/1.1\r\n\r\n").is_none());
+    assert!(parse_request("GET /other?q=hi HTTP/1.1\r\n\r\n").is_none());
+
+    let export = parse_export_request("GET /export?session=alice&search=two+lobes&limit=5&format=csv HTTP/1.1\r\n\r\n").unwrap();
+    assert_eq!(export, ExportRequest { session_id: Some("alice".to_string()), search: Some("two lobes".to_string()), limit: 5, format: ExportFormat::Csv });
+    assert_eq!(parse_export_request("GET /export?session=&search= HTTP/1.1\r\n\r\n").unwrap(), ExportRequest { session_id: None, search: None, limit: 100, format: ExportFormat::Json });
+    assert!(parse_export_request("GET /export?format=xml HTTP/1.1\r\n\r\n").is_none());
+    assert!(parse_export_request("GET /query?q=hi HTTP/1.1\r\n\r\n").is_none());
+}
+
+fn canned(request: QueryRequest) -> UnboundedReceiverStream<ResponseEvent> {
+    let (sender, receiver) = tokio::sync::mpsc::unbounded_channel();
+    sender.send(ResponseEvent::Chunk { text: format!("{} asked {}.", request.session_id, request.query) }).unwrap();
+    sender.send(ResponseEvent::Refinement { depth: 1, strategy: "trim", score: 0.5, best: true }).unwrap();
+    sender.send(ResponseEvent::Done { response: "ok".to_string(), score: 0.5, stop_reason: StopReason::Converged }).unwrap();
+    UnboundedReceiverStream::new(receiver)
+}
+
+async fn get(address: std::net::SocketAddr, request: &str) -> String {
+    let mut socket = TcpStream::connect(address).await.unwrap();
+    socket.write_all(request.as_bytes()).await.unwrap();
+    let mut response = String::new();
+    socket.read_to_string(&mut response).await.unwrap();
+    response
+}
+
+#[tokio::test]
+async fn test_local_api_relays_events_as_sse_and_ndjson() {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let address = listener.local_addr().unwrap();
+    tokio::spawn(LocalApi::new(canned).serve(listener));
+
+    let sse = get(address, "GET /query?q=hi&session=bob HTTP/1.1\r\naccept: text/event-stream\r\n\r\n").await;
+    assert!(sse.starts_with("HTTP/1.1 200 OK\r\ncontent-type: text/event-stream\r\n"), "{sse}");
+    assert!(sse.contains("event: chunk\ndata: {\"event\":\"chunk\",\"text\":\"bob asked hi.\"}\n\n"), "{sse}");
+    assert!(sse.contains("event: done\ndata: {\"event\":\"done\",\"response\":\"ok\",\"score\":0.5,\"stop_reason\":\"converged\"}\n\n"));
+    assert!(sse.ends_with("\r\n0\r\n\r\n"));
+
+    let ndjson = get(address, "GET /query?q=hi HTTP/1.1\r\n\r\n").await;
+    let body = ndjson.split_once("\r\n\r\n").unwrap().1;
+    let lines: Vec<&str> = body.split("\r\n").collect();
+    // Chunk size, chunk, ... terminated by a zero-size chunk.
+    assert_eq!(usize::from_str_radix(lines[0], 16).unwrap(), lines[1].len());
+    assert_eq!(lines[3], "{\"event\":\"refinement\",\"depth\":1,\"strategy\":\"trim\",\"score\":0.5,\"best\":true}\n");
+    assert!(body.ends_with("0\r\n\r\n"));
+
+    assert!(get(address, "GET /missing HTTP/1.1\r\n\r\n").await.starts_with("HTTP/1.1 404"));
+}
+
+#[tokio::test]
+async fn test_local_api_exports_the_dialogue_log() {
+    use crate::core::dialogue_frame::{DialogueFrame, Speaker};
+    use crate::core::dialogue_sessions::DialogueSessions;
+    use crate::storage::state_manager::StateManager;
+    let peer_id = format!("export_api_test_{}", std::process::id());
+    let sessions = DialogueSessions::new(8).with_persistence(StateManager::new(peer_id.clone()));
+    sessions.record(DialogueFrame::new("alice", Speaker::User, "Why two lobes?", 0));
+    sessions.record(DialogueFrame::new("bob", Speaker::User, "Hello", 0));
+    let manager = sessions.persistence().unwrap().clone();
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let address = listener.local_addr().unwrap();
+    let api = LocalApi::new(canned).with_exporter(move |request| export_dialogue(&manager, request.session_id.as_deref(), request.search.as_deref(), request.limit, request.format));
+    tokio::spawn(api.serve(listener));
+
+    let csv = get(address, "GET /export?session=alice&format=csv HTTP/1.1\r\n\r\n").await;
+    assert!(csv.starts_with("HTTP/1.1 200 OK\r\ncontent-type: text/csv\r\n"), "{csv}");
+    let body = csv.split_once("\r\n\r\n").unwrap().1;
+    assert_eq!(body.lines().count(), 2);
+    assert!(body.contains("\"Why two lobes?\""));
+    let json = get(address, "GET /export?search=hello HTTP/1.1\r\n\r\n").await;
+    let exported: serde_json::Value = serde_json::from_str(json.split_once("\r\n\r\n").unwrap().1).unwrap();
+    assert_eq!(exported.as_array().unwrap().len(), 1);
+    assert_eq!(exported[0]["session_id"], "bob");
+    assert!(get(address, "GET /export?format=xml HTTP/1.1\r\n\r\n").await.starts_with("HTTP/1.1 404"));
+    std::fs::remove_file(format!("backup_{}.sqlite", peer_id)).unwrap();
+}
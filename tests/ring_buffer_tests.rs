@@ -0,0 +1,76 @@
+use std::collections::VecDeque;
+use proptest::prelude::*;
+use crate::core::ring_buffer::{RingBuffer, Timestamped};
+
+#[derive(Clone, Debug)]
+enum Op {
+    Append(u32),
+    Drain,
+    Resize(usize),
+}
+
+fn op_strategy() -> impl Strategy<Value = Op> {
+    prop_oneof![
+        8 => any::<u32>().prop_map(Op::Append),
+        1 => Just(Op::Drain),
+        1 => (0usize..12).prop_map(Op::Resize),
+    ]
+}
+
+proptest! {
+    #[test]
+    fn test_matches_vecdeque_reference(capacity in 0usize..10, ops in prop::collection::vec(op_strategy(), 0..200)) {
+        let mut buffer = RingBuffer::new(capacity);
+        let mut reference: VecDeque<u32> = VecDeque::new();
+        let mut reference_capacity = capacity;
+        for op in ops {
+            match op {
+                Op::Append(value) => {
+                    let evicted = buffer.append(value);
+                    reference.push_back(value);
+                    let expected = if reference.len() > reference_capacity { reference.pop_front() } else { None };
+                    prop_assert_eq!(evicted, expected);
+                }
+                Op::Drain => {
+                    let drained: Vec<_> = buffer.drain().collect();
+                    prop_assert_eq!(drained, reference.drain(..).collect::<Vec<_>>());
+                }
+                Op::Resize(new_capacity) => {
+                    buffer.resize(new_capacity);
+                    while reference.len() > new_capacity { reference.pop_front(); }
+                    reference_capacity = new_capacity;
+                }
+            }
+            prop_assert_eq!(buffer.len(), reference.len());
+            prop_assert_eq!(buffer.is_empty(), reference.is_empty());
+            prop_assert_eq!(buffer.iter().copied().collect::<Vec<_>>(), reference.iter().copied().collect::<Vec<_>>());
+            prop_assert_eq!(buffer.iter_rev().copied().collect::<Vec<_>>(), reference.iter().rev().copied().collect::<Vec<_>>());
+            prop_assert_eq!(buffer.get_latest(), reference.back());
+            for k in 0..=reference.len() + 1 {
+                let expected: Vec<_> = reference.iter().skip(reference.len().saturating_sub(k)).copied().collect();
+                prop_assert_eq!(buffer.last_n(k).copied().collect::<Vec<_>>(), expected);
+            }
+            for i in 0..=reference.len() {
+                prop_assert_eq!(buffer.get(i), reference.get(i));
+            }
+        }
+    }
+}
+
+#[derive(Clone)]
+struct Tick(chrono::DateTime<chrono::Utc>);
+
+impl Timestamped for Tick {
+    fn timestamp(&self) -> chrono::DateTime<chrono::Utc> { self.0 }
+}
+
+#[test]
+fn test_timestamp_window() {
+    let start = chrono::Utc::now();
+    let mut buffer = RingBuffer::new(5);
+    buffer.extend((0..8).map(|i| Tick(start + chrono::Duration::seconds(i))));
+    let window: Vec<_> = buffer.window(start + chrono::Duration::seconds(4), start + chrono::Duration::seconds(6)).collect();
+    assert_eq!(window.len(), 2);
+    assert_eq!(window[0].0, start + chrono::Duration::seconds(4));
+    assert_eq!(buffer.since(start).count(), 5);
+}
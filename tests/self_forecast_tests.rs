@@ -0,0 +1,69 @@
+use chrono::{Duration, TimeZone, Utc};
+use crate::core::self_forecast::{ArForecaster, SelfForecaster, SelfVariable};
+use crate::core::self_model::{SelfModel, SelfObservation};
+
+#[test]
+fn test_ar_forecaster_learns_an_ar2_process() {
+    let mut model = ArForecaster::new(2, 1.0);
+    let (mut previous, mut current) = (0.0, 1.0);
+    assert_eq!(model.update(previous), None);
+    assert_eq!(model.update(current), None);
+    let mut last_error = f64::MAX;
+    for _ in 0..60 {
+        let next = 1.5 * current - 0.7 * previous + 1.0;
+        last_error = model.update(next).unwrap();
+        (previous, current) = (current, next);
+    }
+    assert!(last_error.abs() < 1e-4, "{last_error}");
+    let weights = model.weights();
+    assert!((weights[0] - 1.0).abs() < 1e-2 && (weights[1] - 1.5).abs() < 1e-2 && (weights[2] + 0.7).abs() < 1e-2, "{weights:?}");
+    assert!((model.forecast().unwrap() - (1.5 * current - 0.7 * previous + 1.0)).abs() < 1e-4);
+}
+
+#[test]
+fn test_constant_input_keeps_the_forecast_finite() {
+    // Without a bound on P this overflows to inf/NaN after roughly 35k ticks.
+    let mut model = ArForecaster::new(2, 0.98);
+    for _ in 0..100_000 { model.update(3.0); }
+    let forecast = model.forecast().unwrap();
+    assert!((forecast - 3.0).abs() < 1e-6, "{forecast}");
+    assert!(model.weights().iter().all(|weight| weight.is_finite()));
+    // Still adapts when the input finally moves.
+    for _ in 0..50 { model.update(7.0); }
+    assert!((model.forecast().unwrap() - 7.0).abs() < 1e-3);
+}
+
+fn tick(t: i64, entropy: f64) -> SelfObservation {
+    SelfObservation { entropy, pleasure: (t as f64 * 0.2).sin() * 0.5, cosmic_entropy: 2.0, ..Default::default() }
+}
+
+#[test]
+fn test_forecast_errors_are_logged_and_spikes_alert() {
+    let start = Utc.with_ymd_and_hms(2025, 3, 25, 0, 0, 0).unwrap();
+    let mut forecaster = SelfForecaster::new(2);
+    for t in 0..200 {
+        let alerts = forecaster.observe_at(&tick(t, 5.0 + (t as f64 * 0.3).sin()), start + Duration::seconds(t));
+        assert!(alerts.is_empty(), "tick {t}: {alerts:?}");
+    }
+    assert_eq!(forecaster.error_log().len(), 3 * 198);
+    assert!(forecaster.mean_error(SelfVariable::Entropy, 20).unwrap() < 1e-3);
+    assert!(forecaster.mean_error(SelfVariable::CosmicEntropy, 20).unwrap() < 1e-4);
+    let alerts = forecaster.observe_at(&tick(200, 40.0), start + Duration::seconds(200));
+    assert_eq!(alerts.len(), 1);
+    assert_eq!(alerts[0].variable, SelfVariable::Entropy);
+    assert!(alerts[0].error > 30.0);
+    assert_eq!(forecaster.alerts().len(), 1);
+    let record = forecaster.error_log().iter_rev().find(|record| record.variable == SelfVariable::Entropy).unwrap();
+    assert_eq!((record.actual, record.timestamp), (40.0, start + Duration::seconds(200)));
+}
+
+#[test]
+fn test_self_model_reports_forecasts() {
+    let mut model = SelfModel::new();
+    for t in 0..50 { model.observe(tick(t, t as f64)); }
+    let report = model.introspect();
+    assert!((report.forecast.entropy.unwrap() - 50.0).abs() < 1e-3);
+    assert!((report.forecast.cosmic_entropy.unwrap() - 2.0).abs() < 1e-3);
+    assert!(report.forecast_errors.valence.unwrap() < 0.05);
+    assert!(model.observe(tick(50, -100.0)).iter().any(|alert| alert.variable == SelfVariable::Entropy));
+}
@@ -5,3 +5,48 @@ async fn test_recursive_depth() {
     model.update_self(&node);
     assert!(model.get_recursive_depth() > 0.0);
 }
+
+fn observe_entropy(model: &mut crate::core::self_model::SelfModel, series: impl IntoIterator<Item = f64>) {
+    for entropy in series {
+        model.observe(crate::core::self_model::SelfObservation { entropy, ..Default::default() });
+    }
+}
+
+#[test]
+fn test_depth_tracks_self_predictability() {
+    use crate::core::self_model::SelfModel;
+    assert_eq!(SelfModel::new().get_recursive_depth(), 0.0);
+    let mut ramp = SelfModel::new();
+    observe_entropy(&mut ramp, (0..40).map(|t| t as f64 * 0.5));
+    let mut erratic = SelfModel::new();
+    observe_entropy(&mut erratic, (0..40).map(|t| if t % 2 == 0 { 0.0 } else { 10.0 }));
+    let (deep, shallow) = (ramp.get_recursive_depth(), erratic.get_recursive_depth());
+    assert!(deep > 4.0 && deep <= 5.0, "{deep}");
+    assert!(shallow < 1.2, "{shallow}");
+    let report = ramp.introspect();
+    assert_eq!(report.levels.len(), 4);
+    assert!(report.levels.iter().all(|level| level.prediction.is_some()));
+    // Smoothing by half settles into lagging a ramp of slope 0.5 by 1.
+    assert!((report.entropy_prediction_error.unwrap() - 1.0).abs() < 1e-9);
+    assert_eq!(report.depth, deep);
+}
+
+#[test]
+fn test_introspection_report() {
+    use crate::core::emotional_contagion::EmotionalSummary;
+    use crate::core::self_model::{SelfModel, SelfObservation};
+    let mut model = SelfModel::new();
+    let report = model.introspect();
+    assert_eq!((report.observations, report.entropy_prediction, report.peer_agreement), (0, None, None));
+    model.observe(SelfObservation { entropy: 2.0, pleasure: 0.5, arousal: 0.2, dominance: 0.0, cosmic_entropy: 1.5 });
+    model.observe(SelfObservation { entropy: 4.0, pleasure: -0.5, arousal: 0.2, dominance: 0.0, cosmic_entropy: 1.5 });
+    let neighbor = |peer_id: &str, pleasure: f64| EmotionalSummary { peer_id: peer_id.to_string(), pleasure, arousal: 0.2, dominance: 0.0, cosmic_resonance: 0.0, timestamp_millis: 0 };
+    model.observe_peer_views(&[neighbor("a", -0.5), neighbor("b", 0.7)]);
+    let report = model.introspect();
+    assert_eq!(report.observations, 2);
+    assert_eq!((report.observation.entropy, report.observation.pleasure, report.observation.cosmic_entropy), (4.0, -0.5, 1.5));
+    assert_eq!((report.entropy_prediction, report.entropy_prediction_error), (Some(3.0), Some(2.0)));
+    assert!((report.emotional_stability - 0.5).abs() < 1e-12);
+    assert!((report.peer_agreement.unwrap() - 0.9).abs() < 1e-12);
+    assert_eq!((model.entropy(), model.valence(), model.cosmic_entropy()), (4.0, -0.5, 1.5));
+}
@@ -5,3 +5,114 @@ async fn test_field_update() {
     tensor.update_field(&node);
     assert!(tensor.cosmic_entropy > 0.0);
 }
+
+#[test]
+fn test_shard_count_invariance() {
+    use crate::quantum::quantum_fractal_tensor_engine::QuantumFractalTensorEngine;
+    let mut reference = QuantumFractalTensorEngine::with_config([37, 29], 1, 7);
+    reference.evolve(8);
+    for shard_count in [2, 3, 5, 37, 64] {
+        let mut engine = QuantumFractalTensorEngine::with_config([37, 29], shard_count, 7);
+        engine.evolve(8);
+        assert_eq!(engine.tensor_field, reference.tensor_field);
+    }
+    assert!(reference.tensor_field.as_slice().iter().any(|v| *v != 0.0));
+}
+
+#[test]
+fn test_3d_orthogonal_slices() {
+    use crate::quantum::quantum_fractal_tensor_engine::QuantumFractalTensorEngine3D;
+    let mut engine = QuantumFractalTensorEngine3D::with_config([4, 5, 6], 2, 7);
+    for x in 0..4 { for y in 0..5 { for z in 0..6 {
+        engine.tensor_field[[x, y, z]] = (x * 100 + y * 10 + z) as f64;
+    } } }
+    let xy = engine.tensor_field.plane(0, 1, [0, 0, 3]);
+    let yz = engine.tensor_field.plane(1, 2, [2, 0, 0]);
+    assert_eq!((xy.width, xy.height), (4, 5));
+    assert_eq!(xy.get(3, 4), 343.0);
+    assert_eq!(yz.get(4, 5), 245.0);
+    let mut sharded = engine.clone();
+    sharded.set_shard_count(4);
+    engine.evolve(3);
+    sharded.evolve(3);
+    assert_eq!(engine.tensor_field, sharded.tensor_field);
+}
+
+#[test]
+fn test_field_rules_produce_structure() {
+    use crate::quantum::field_rules::{ComplexIteratedMap, CoupledLogisticLattice, Diffusion, GrayScott, LifeLike};
+    use crate::quantum::quantum_fractal_tensor_engine::QuantumFractalTensorEngine;
+    let mut heat = QuantumFractalTensorEngine::with_config([32, 32], 3, 1);
+    heat.set_rule(Diffusion::new(0.2));
+    let total: f64 = heat.tensor_field.as_slice().iter().sum();
+    heat.evolve(20);
+    assert!((heat.tensor_field.as_slice().iter().sum::<f64>() - total).abs() < 1e-9);
+
+    let mut life = QuantumFractalTensorEngine::with_config([16, 16], 2, 1);
+    life.set_rule(LifeLike::conway());
+    life.tensor_field = crate::quantum::field_tensor::FieldTensor::zeros([16, 16]);
+    for j in 4..7 { life.tensor_field[[5, j]] = 1.0; }
+    life.evolve(2);
+    assert_eq!(life.tensor_field[[5, 4]], 1.0);
+    assert_eq!(life.tensor_field.as_slice().iter().sum::<f64>(), 3.0);
+
+    let mut gray_scott = QuantumFractalTensorEngine::with_config([48, 48], 4, 1);
+    gray_scott.set_rule(GrayScott::new());
+    gray_scott.evolve(200);
+    assert_eq!(gray_scott.aux_fields().len(), 1);
+    assert!(gray_scott.tensor_field.as_slice().iter().all(|v| v.is_finite()));
+
+    let mut lattice = QuantumFractalTensorEngine::with_config([16, 16], 1, 1);
+    lattice.set_rule(CoupledLogisticLattice::new());
+    lattice.evolve(50);
+    assert!(lattice.tensor_field.as_slice().iter().all(|v| (0.0..=1.0).contains(v)));
+
+    let mut mandelbrot = QuantumFractalTensorEngine::with_config([32, 32], 2, 1);
+    mandelbrot.set_rule(ComplexIteratedMap::mandelbrot());
+    mandelbrot.evolve(64);
+    assert!(mandelbrot.tensor_field[[16, 12]] > 0.9);
+    assert!(mandelbrot.tensor_field[[0, 0]] < 0.2);
+}
+
+#[test]
+fn test_row_kernels_match_per_cell_updates() {
+    use crate::quantum::field_rules::{FieldRule, GrayScott, Neighborhood, QuantumDecay};
+    use crate::quantum::quantum_fractal_tensor_engine::QuantumFractalTensorEngine;
+
+    struct PerCell<R>(R);
+    impl<R: FieldRule<3>> FieldRule<3> for PerCell<R> {
+        fn name(&self) -> &'static str { "per_cell" }
+        fn channels(&self) -> usize { self.0.channels() }
+        fn initial_value(&self, channel: usize, position: [f64; 3], noise: f64) -> f64 { self.0.initial_value(channel, position, noise) }
+        fn update(&self, cell: &Neighborhood<'_, 3>, out: &mut [f64]) { self.0.update(cell, out) }
+    }
+
+    let mut fast = QuantumFractalTensorEngine::with_config([9, 7, 5], 3, 11);
+    let mut slow = fast.clone();
+    fast.set_rule(GrayScott::new());
+    slow.set_rule(PerCell(GrayScott::new()));
+    fast.evolve(6);
+    slow.evolve(6);
+    assert_eq!(fast.tensor_field, slow.tensor_field);
+    assert_eq!(fast.aux_fields(), slow.aux_fields());
+
+    fast.set_rule(QuantumDecay::new());
+    slow.set_rule(PerCell(QuantumDecay::new()));
+    fast.evolve(6);
+    slow.evolve(6);
+    assert_eq!(fast.tensor_field, slow.tensor_field);
+}
+
+#[test]
+fn test_strided_views() {
+    let field = crate::quantum::field_tensor::FieldTensor::from_data([2, 3, 4], (0..24).map(f64::from).collect());
+    let view = field.view();
+    assert_eq!(view.row([1, 2, 0]), &[20.0, 21.0, 22.0, 23.0]);
+    let slab = view.narrow(1, 1..3);
+    assert_eq!(slab.shape(), [2, 2, 4]);
+    assert_eq!(slab.get([1, 0, 1]), 17.0);
+    let transposed = view.permuted([2, 1, 0]);
+    assert_eq!(transposed.get([3, 2, 1]), field[[1, 2, 3]]);
+    assert!(!transposed.rows_contiguous());
+    assert_eq!(view.fix(0, 1).to_tensor().as_slice(), &field.as_slice()[12..]);
+}
@@ -0,0 +1,57 @@
+use crate::core::dialogue_frame::{DialogueFrame, Speaker};
+use crate::core::elias_nlp_interface::most_relevant;
+use crate::core::emotional_analytics::Mood;
+use crate::core::response_templates::{render, ResponseContext};
+use crate::core::text_pipeline::{analyze, normalize, tokenize, Intent};
+
+#[test]
+fn test_normalization_tokens_and_keywords() {
+    assert_eq!(normalize("  I  DON'T   know\tWhy "), "i do not know why");
+    assert_eq!(normalize("They're reading O'Reilly, aren't they? I'd say so."), "they are reading o'reilly, are not they? i would say so.");
+    assert_eq!(normalize("Can't the d'Alembert 'red' fit?"), "can not the d'alembert 'red' fit?");
+    assert_eq!(tokenize("fractals, entropy & the network's edge!"), vec!["fractals", "entropy", "the", "networks", "edge"]);
+    let analysis = analyze("Explain the Lorenz attractor and why the Lorenz system is chaotic.");
+    assert_eq!(analysis.keywords, vec!["lorenz", "attractor", "system", "chaotic"]);
+}
+
+#[test]
+fn test_intents() {
+    let cases = [
+        ("Hello there", Intent::Greeting),
+        ("goodbye!", Intent::Farewell),
+        ("What is cosmic entropy?", Intent::Question),
+        ("Entropy rises at night?", Intent::Question),
+        ("Please describe the Julia set", Intent::Command),
+        ("I feel really happy today", Intent::Feeling),
+        ("The network grew overnight", Intent::Statement),
+    ];
+    for (query, expected) in cases {
+        assert_eq!(analyze(query).intent, expected, "{query}");
+    }
+}
+
+#[test]
+fn test_sentiment_scoring() {
+    let plain = analyze("this is good").sentiment;
+    assert!(plain > 0.3 && plain < 1.0);
+    assert!(analyze("this is very good").sentiment > plain);
+    assert!(analyze("this is not good").sentiment < 0.0);
+    assert!(analyze("terrible, awful, broken").sentiment < -0.8);
+    assert_eq!(analyze("the attractor has two lobes").sentiment, 0.0);
+}
+
+#[test]
+fn test_response_references_query_mood_and_history() {
+    let history = vec![
+        DialogueFrame::new("default", Speaker::User, "The Julia set looked beautiful", 0),
+        DialogueFrame::new("default", Speaker::User, "Peers keep dropping", 0),
+    ];
+    let analysis = analyze("Why does the Julia set change with valence?");
+    let related = most_relevant(&history, &analysis);
+    assert_eq!(related.unwrap().content, "The Julia set looked beautiful");
+    let response = render(&ResponseContext { analysis: &analysis, mood: Mood::Relaxed, valence: 0.4, entropy: 3.5, cosmic_entropy: 1.25, related, history: &history });
+    assert!(response.starts_with("Elias v4.4.1 reflects on your question about julia, set, change and valence."), "{response}");
+    assert!(response.contains("I feel relaxed (valence +0.40) at entropy 3.50, cosmic 1.25."));
+    assert!(response.ends_with("Earlier you mentioned \"The Julia set looked beautiful\"."));
+    assert!(most_relevant(&history, &analyze("hello")).is_none());
+}